@@ -55,15 +55,8 @@ impl PraedaGodotGenerator {
         max: f64, 
         required: bool
     ) -> bool {
-        let attr = ItemAttribute {
-            name: attr_name,
-            initial_value,
-            min,
-            max,
-            required,
-            scaling_factor: 1.0, // Default
-            chance: 1.0,         // Default
-        };
+        let mut attr = ItemAttribute::new(&attr_name, initial_value, min, max, required);
+        attr.set_chance(1.0); // Default
         
         // Handle empty strings as "all" or "none" depending on logic, 
         // but here we pass them through as the Rust API expects specific matching
@@ -92,6 +85,7 @@ impl PraedaGodotGenerator {
             affix_chance: options.get("affix_chance").map(|v| v.to::<f64>()).unwrap_or(0.5),
             linear: options.get("linear").map(|v| v.to::<bool>()).unwrap_or(true),
             scaling_factor: options.get("scaling_factor").map(|v| v.to::<f64>()).unwrap_or(1.0),
+            ..Default::default()
         };
 
         let overrides = GeneratorOverrides::empty(); // Simplified for this example
@@ -106,9 +100,11 @@ impl PraedaGodotGenerator {
                 dict.set("type", item.item_type);
                 dict.set("subtype", item.subtype);
                 
-                // Convert attributes to dictionary
+                // Convert attributes to dictionary, ordered by name
                 let mut attrs_dict = Dictionary::new();
-                for (name, attr) in item.attributes {
+                let mut attributes: Vec<_> = item.attributes.into_iter().collect();
+                attributes.sort_by(|a, b| a.0.cmp(&b.0));
+                for (name, attr) in attributes {
                     attrs_dict.set(name, attr.initial_value);
                 }
                 dict.set("attributes", attrs_dict);
@@ -24,6 +24,7 @@ namespace Praeda {
         public byte Required;
         public double ScalingFactor;
         public double Chance;
+        public byte Format;
     }
 
     /// <summary>
@@ -190,6 +191,7 @@ namespace Praeda {
         public bool Required { get; set; }
         public double ScalingFactor { get; set; } = 1.0;
         public double Chance { get; set; }
+        public byte Format { get; set; }
 
         public ItemAttribute() { }
         public ItemAttribute(string name, double initialValue, double min, double max, bool required) {
@@ -210,7 +212,8 @@ namespace Praeda {
                 Max = cAttr.Max,
                 Required = cAttr.Required != 0,
                 ScalingFactor = cAttr.ScalingFactor,
-                Chance = cAttr.Chance
+                Chance = cAttr.Chance,
+                Format = cAttr.Format
             };
         }
     }
@@ -204,6 +204,7 @@ fn main() -> Result<()> {
         affix_chance: args.affix_chance,
         linear,
         scaling_factor: args.scaling_factor,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "cli")?;
@@ -18,6 +18,12 @@
 // ============================================================================
 
 extern "C" {
+    // Error codes returned by load/generate calls (-1 is the generic fallback)
+    #define PRAEDA_ERROR_GENERIC        (-1)
+    #define PRAEDA_ERROR_INVALID_TOML   (-2)
+    #define PRAEDA_ERROR_MISSING_CONFIG (-3)
+    #define PRAEDA_ERROR_GENERATION     (-4)
+
     // Handle types (opaque pointers)
     typedef struct PraedaGeneratorHandle PraedaGeneratorHandle;
     typedef struct CItemArrayHandle CItemArrayHandle;
@@ -31,6 +37,7 @@ extern "C" {
         uint8_t required;
         double scaling_factor;
         double chance;
+        uint8_t format;  // 0 = flat, 1 = percent, 2 = per second, 3 = duration
     } CItemAttribute;
 
     typedef struct {
@@ -56,6 +63,7 @@ extern "C" {
     void praeda_string_free(char* ptr);
     void praeda_error_free(char* ptr);
     void praeda_item_array_free(CItemArrayHandle* handle);
+    void praeda_item_free(CItem* item);
 
     // Configuration
     int praeda_generator_load_toml(
@@ -95,6 +103,19 @@ extern "C" {
         int required
     );
 
+    int praeda_generator_set_attribute_ex(
+        PraedaGeneratorHandle* handle,
+        const char* type_name,
+        const char* subtype_name,
+        const char* attr_name,
+        double initial_value,
+        double min_value,
+        double max_value,
+        int required,
+        double chance,
+        double scaling_factor
+    );
+
     int praeda_generator_set_item_names(
         PraedaGeneratorHandle* handle,
         const char* type_name,
@@ -139,13 +160,51 @@ extern "C" {
         char** error_out
     );
 
+    // Returns an indented JSON array of items; free with praeda_string_free
+    char* praeda_generator_generate_loot_json_pretty(
+        PraedaGeneratorHandle* handle,
+        uint32_t number_of_items,
+        double base_level,
+        double level_variance,
+        double affix_chance,
+        uint8_t linear,
+        double scaling_factor,
+        char** error_out
+    );
+
+    int praeda_generator_generate_into(
+        PraedaGeneratorHandle* handle,
+        CItem* buf,
+        uint32_t capacity,
+        uint32_t number_of_items,
+        double base_level,
+        double level_variance,
+        double affix_chance,
+        uint8_t linear,
+        double scaling_factor,
+        uint32_t* out_written,
+        char** error_out
+    );
+
     // Item array access
     uint32_t praeda_item_array_count(const CItemArrayHandle* handle);
+    // Returns NULL when index >= count
     const CItem* praeda_item_array_get(const CItemArrayHandle* handle, uint32_t index);
 
+    // Item queries
+    int praeda_item_has_prefix(const CItem* item);
+    int praeda_item_has_suffix(const CItem* item);
+    int praeda_item_get_attribute(const CItem* item, const char* name, CItemAttribute* out);
+
     // Queries
     int praeda_generator_has_quality(const PraedaGeneratorHandle* handle, const char* quality);
+    // Returns the number of config issues (0 = valid); out_json receives a JSON array of messages
+    int praeda_generator_validate(const PraedaGeneratorHandle* handle, char** out_json);
     char* praeda_version(void);
+    // Error code of the last load_toml, generate_loot, generate_loot_json_pretty or
+    // generate_into call on this thread (0 on success). Setters, queries and other calls do
+    // not update it, so after a setter returns -1 this still reports the earlier call's code.
+    int praeda_last_error_code(void);
 }
 
 // ============================================================================
@@ -197,11 +256,11 @@ public:
 class ItemAttribute {
 public:
     ItemAttribute() : initial_value(0.0), min(0.0), max(0.0), required(false),
-                      scaling_factor(1.0), chance(0.0) {}
+                      scaling_factor(1.0), chance(0.0), format(0) {}
 
     ItemAttribute(const std::string& n, double iv, double min_val, double max_val, bool req)
         : name(n), initial_value(iv), min(min_val), max(max_val), required(req),
-          scaling_factor(1.0), chance(0.0) {}
+          scaling_factor(1.0), chance(0.0), format(0) {}
 
     std::string name;
     double initial_value;
@@ -210,6 +269,7 @@ public:
     bool required;
     double scaling_factor;
     double chance;
+    uint8_t format;
 
     static ItemAttribute from_c(const CItemAttribute& c_attr) {
         ItemAttribute attr;
@@ -220,6 +280,7 @@ public:
         attr.required = c_attr.required != 0;
         attr.scaling_factor = c_attr.scaling_factor;
         attr.chance = c_attr.chance;
+        attr.format = c_attr.format;
         return attr;
     }
 };
@@ -374,7 +435,7 @@ public:
     /// Set attribute for an item type/subtype
     void set_attribute(const std::string& type_name, const std::string& subtype_name,
                        const ItemAttribute& attribute) {
-        int result = praeda_generator_set_attribute(
+        int result = praeda_generator_set_attribute_ex(
             handle_,
             type_name.c_str(),
             subtype_name.c_str(),
@@ -382,7 +443,9 @@ public:
             attribute.initial_value,
             attribute.min,
             attribute.max,
-            attribute.required ? 1 : 0
+            attribute.required ? 1 : 0,
+            attribute.chance,
+            attribute.scaling_factor
         );
         if (result != 0) {
             throw Exception("Failed to set attribute");
@@ -14,6 +14,18 @@ pub enum PraedaError {
     #[error("TOML deserialization error: {0}")]
     TomlDeError(#[from] toml::de::Error),
 
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack serialization error: {0}")]
+    MsgPackError(#[from] rmp_serde::encode::Error),
+
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack deserialization error: {0}")]
+    MsgPackDeError(#[from] rmp_serde::decode::Error),
+
+    #[cfg(feature = "bincode")]
+    #[error("Bincode error: {0}")]
+    BincodeError(#[from] bincode::Error),
+
     #[error("File not found: {0}")]
     FileNotFound(String),
 
@@ -28,6 +40,12 @@ pub enum PraedaError {
 
     #[error("Missing item subtype: type={0}, subtype={1}")]
     MissingItemSubtype(String, String),
+
+    #[error("No item names configured: type={0}, subtype={1}")]
+    MissingItemNames(String, String),
+
+    #[error("Item name list is empty: type={0}, subtype={1}")]
+    EmptyItemNames(String, String),
 }
 
 pub type Result<T> = std::result::Result<T, PraedaError>;
@@ -11,9 +11,48 @@
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
 use crate::*;
+use std::cell::Cell;
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_uint};
 
+// ============================================================================
+// Error Codes
+// ============================================================================
+
+/// Generic failure: invalid arguments, I/O errors, or anything without a more specific code
+pub const PRAEDA_ERROR_GENERIC: i32 = -1;
+/// The TOML configuration could not be parsed
+pub const PRAEDA_ERROR_INVALID_TOML: i32 = -2;
+/// An override named a quality, item type or subtype that is not configured, or item names
+/// are required but missing
+pub const PRAEDA_ERROR_MISSING_CONFIG: i32 = -3;
+/// Generation failed, e.g. nothing to select from or invalid generation options
+pub const PRAEDA_ERROR_GENERATION: i32 = -4;
+
+thread_local! {
+    static LAST_ERROR_CODE: Cell<i32> = const { Cell::new(0) };
+}
+
+/// Map a [`PraedaError`] to its FFI error code
+fn error_code(error: &PraedaError) -> i32 {
+    match error {
+        PraedaError::TomlError(_) | PraedaError::TomlDeError(_) => PRAEDA_ERROR_INVALID_TOML,
+        PraedaError::MissingQuality(_)
+        | PraedaError::MissingItemType(_)
+        | PraedaError::MissingItemSubtype(_, _)
+        | PraedaError::MissingItemNames(_, _)
+        | PraedaError::EmptyItemNames(_, _) => PRAEDA_ERROR_MISSING_CONFIG,
+        PraedaError::InvalidData(_) => PRAEDA_ERROR_GENERATION,
+        _ => PRAEDA_ERROR_GENERIC,
+    }
+}
+
+/// Record the result of an FFI call for [`praeda_last_error_code`] and return it
+fn set_last_error_code(code: i32) -> i32 {
+    LAST_ERROR_CODE.with(|last| last.set(code));
+    code
+}
+
 // ============================================================================
 // C-Compatible Struct Definitions
 // ============================================================================
@@ -28,6 +67,8 @@ pub struct CItemAttribute {
     pub required: u8,
     pub scaling_factor: f64,
     pub chance: f64,
+    /// [`AttributeFormat`] discriminant: 0 = flat, 1 = percent, 2 = per second, 3 = duration
+    pub format: u8,
 }
 
 impl CItemAttribute {
@@ -43,6 +84,7 @@ impl CItemAttribute {
             required: if attr.required { 1 } else { 0 },
             scaling_factor: attr.scaling_factor,
             chance: attr.chance,
+            format: attr.format as u8,
         }
     }
 
@@ -84,6 +126,11 @@ impl CAffix {
         }
     }
 
+    /// An affix with a null or empty name means no affix was applied
+    fn is_present(&self) -> bool {
+        !self.name.is_null() && unsafe { *self.name } != 0
+    }
+
     fn free(&mut self) {
         if !self.name.is_null() {
             unsafe {
@@ -124,9 +171,9 @@ pub struct CItem {
 impl CItem {
     fn from_rust(item: &Item) -> Self {
         let attributes: Vec<CItemAttribute> = item
-            .attributes
-            .values()
-            .map(CItemAttribute::from_rust)
+            .get_attributes_ordered()
+            .into_iter()
+            .map(|(_, attr)| CItemAttribute::from_rust(attr))
             .collect();
         let attr_count = attributes.len() as c_uint;
         let attrs_ptr = if attr_count > 0 {
@@ -302,6 +349,20 @@ pub extern "C" fn praeda_item_array_free(handle: *mut CItemArrayHandle) {
     }
 }
 
+/// Free the strings and attribute arrays owned by a single item
+///
+/// The `CItem` struct itself is not freed, so this is safe to call on items stored in
+/// caller-provided buffers (see `praeda_generator_generate_into`). Freed fields are nulled,
+/// so calling this twice on the same item is harmless.
+#[unsafe(no_mangle)]
+pub extern "C" fn praeda_item_free(item: *mut CItem) {
+    if !item.is_null() {
+        unsafe {
+            (*item).free();
+        }
+    }
+}
+
 // ============================================================================
 // TOML Configuration
 // ============================================================================
@@ -321,7 +382,7 @@ pub extern "C" fn praeda_generator_load_toml(
                 *error_out = err.into_raw();
             }
         }
-        return -1;
+        return set_last_error_code(PRAEDA_ERROR_GENERIC);
     }
 
     let toml_cstr = unsafe { CStr::from_ptr(toml_str) };
@@ -334,13 +395,13 @@ pub extern "C" fn praeda_generator_load_toml(
                     *error_out = err.into_raw();
                 }
             }
-            return -1;
+            return set_last_error_code(PRAEDA_ERROR_GENERIC);
         }
     };
 
     let generator = unsafe { &mut (*handle).generator };
     match generator.load_data(toml_string) {
-        Ok(_) => 0,
+        Ok(_) => set_last_error_code(0),
         Err(e) => {
             if !error_out.is_null()
                 && let Ok(err) = CString::new(format!("Failed to load TOML: {}", e)) {
@@ -348,7 +409,7 @@ pub extern "C" fn praeda_generator_load_toml(
                     *error_out = err.into_raw();
                 }
             }
-            -1
+            set_last_error_code(error_code(&e))
         }
     }
 }
@@ -445,6 +506,35 @@ pub extern "C" fn praeda_generator_set_attribute(
     min_value: f64,
     max_value: f64,
     required: i32,
+) -> i32 {
+    praeda_generator_set_attribute_ex(
+        handle,
+        type_name,
+        subtype_name,
+        attr_name,
+        initial_value,
+        min_value,
+        max_value,
+        required,
+        0.0,
+        1.0,
+    )
+}
+
+/// Set a fully-specified attribute, including its chance and scaling factor, for an item type/subtype
+/// Returns 0 on success, -1 on failure
+#[unsafe(no_mangle)]
+pub extern "C" fn praeda_generator_set_attribute_ex(
+    handle: *mut PraedaGeneratorHandle,
+    type_name: *const c_char,
+    subtype_name: *const c_char,
+    attr_name: *const c_char,
+    initial_value: f64,
+    min_value: f64,
+    max_value: f64,
+    required: i32,
+    chance: f64,
+    scaling_factor: f64,
 ) -> i32 {
     if handle.is_null() || type_name.is_null() || subtype_name.is_null() || attr_name.is_null() {
         return -1;
@@ -468,13 +558,15 @@ pub extern "C" fn praeda_generator_set_attribute(
         Err(_) => return -1,
     };
 
-    let attribute = ItemAttribute::new(
+    let mut attribute = ItemAttribute::new(
         attr_str,
         initial_value,
         min_value,
         max_value,
         required != 0,
     );
+    attribute.set_chance(chance);
+    attribute.set_scaling_factor(scaling_factor);
 
     let generator = unsafe { &mut (*handle).generator };
     generator.set_attribute(
@@ -674,6 +766,7 @@ pub extern "C" fn praeda_generator_generate_loot(
                 *error_out = err.into_raw();
             }
         }
+        set_last_error_code(PRAEDA_ERROR_GENERIC);
         return std::ptr::null_mut();
     }
 
@@ -684,11 +777,13 @@ pub extern "C" fn praeda_generator_generate_loot(
         affix_chance,
         linear: linear != 0,
         scaling_factor,
+        ..Default::default()
     };
 
     let generator = unsafe { &mut (*handle).generator };
     match generator.generate_loot(&options, &GeneratorOverrides::empty(), "ffi") {
         Ok(items) => {
+            set_last_error_code(0);
             let c_array = CItemArray::from_rust(&items);
             Box::into_raw(Box::new(CItemArrayHandle { array: c_array }))
         }
@@ -699,12 +794,147 @@ pub extern "C" fn praeda_generator_generate_loot(
                     *error_out = err.into_raw();
                 }
             }
+            set_last_error_code(error_code(&e));
             std::ptr::null_mut()
         }
     }
 }
 
+/// Generate loot items as an indented JSON array
+/// Returns the JSON string on success, null on failure
+/// Caller must free returned string with praeda_string_free()
+#[unsafe(no_mangle)]
+pub extern "C" fn praeda_generator_generate_loot_json_pretty(
+    handle: *mut PraedaGeneratorHandle,
+    number_of_items: c_uint,
+    base_level: f64,
+    level_variance: f64,
+    affix_chance: f64,
+    linear: u8,
+    scaling_factor: f64,
+    error_out: *mut *mut c_char,
+) -> *mut c_char {
+    if handle.is_null() {
+        if !error_out.is_null()
+            && let Ok(err) = CString::new("Invalid handle") {
+            unsafe {
+                *error_out = err.into_raw();
+            }
+        }
+        set_last_error_code(PRAEDA_ERROR_GENERIC);
+        return std::ptr::null_mut();
+    }
+
+    let options = GeneratorOptions {
+        number_of_items,
+        base_level,
+        level_variance,
+        affix_chance,
+        linear: linear != 0,
+        scaling_factor,
+        ..Default::default()
+    };
+
+    let generator = unsafe { &mut (*handle).generator };
+    let result = generator
+        .generate_loot_json_pretty(&options, &GeneratorOverrides::empty(), "ffi")
+        .and_then(|json| {
+            CString::new(json)
+                .map_err(|e| PraedaError::InvalidData(format!("JSON contains a nul byte: {}", e)))
+        });
+    match result {
+        Ok(json) => {
+            set_last_error_code(0);
+            json.into_raw()
+        }
+        Err(e) => {
+            if !error_out.is_null()
+                && let Ok(err) = CString::new(format!("Failed to generate loot: {}", e)) {
+                unsafe {
+                    *error_out = err.into_raw();
+                }
+            }
+            set_last_error_code(error_code(&e));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Generate loot items into a caller-provided buffer
+///
+/// Writes at most `capacity` items into `buf` and stores the number written in `out_written`.
+/// The caller owns the buffer; each filled slot owns heap allocations that must be released
+/// with `praeda_item_free`. Strings that cannot be represented as C strings are left null.
+/// Returns 0 on success, -1 on failure
+#[unsafe(no_mangle)]
+pub extern "C" fn praeda_generator_generate_into(
+    handle: *mut PraedaGeneratorHandle,
+    buf: *mut CItem,
+    capacity: c_uint,
+    number_of_items: c_uint,
+    base_level: f64,
+    level_variance: f64,
+    affix_chance: f64,
+    linear: u8,
+    scaling_factor: f64,
+    out_written: *mut c_uint,
+    error_out: *mut *mut c_char,
+) -> i32 {
+    if !out_written.is_null() {
+        unsafe {
+            *out_written = 0;
+        }
+    }
+
+    if handle.is_null() || (buf.is_null() && capacity > 0) {
+        if !error_out.is_null()
+            && let Ok(err) = CString::new("Invalid handle or buffer") {
+            unsafe {
+                *error_out = err.into_raw();
+            }
+        }
+        return set_last_error_code(PRAEDA_ERROR_GENERIC);
+    }
+
+    let options = GeneratorOptions {
+        number_of_items: number_of_items.min(capacity),
+        base_level,
+        level_variance,
+        affix_chance,
+        linear: linear != 0,
+        scaling_factor,
+        ..Default::default()
+    };
+
+    let generator = unsafe { &mut (*handle).generator };
+    match generator.generate_loot(&options, &GeneratorOverrides::empty(), "ffi") {
+        Ok(items) => {
+            for (i, item) in items.iter().enumerate() {
+                unsafe {
+                    std::ptr::write(buf.add(i), CItem::from_rust(item));
+                }
+            }
+            if !out_written.is_null() {
+                unsafe {
+                    *out_written = items.len() as c_uint;
+                }
+            }
+            set_last_error_code(0)
+        }
+        Err(e) => {
+            if !error_out.is_null()
+                && let Ok(err) = CString::new(format!("Failed to generate loot: {}", e)) {
+                unsafe {
+                    *error_out = err.into_raw();
+                }
+            }
+            set_last_error_code(error_code(&e))
+        }
+    }
+}
+
 /// Get items from array handle
+/// Returns null for a null handle or an index >= the array count; never a dangling pointer
 /// Panics if handle is invalid - caller must ensure handle is valid
 #[unsafe(no_mangle)]
 pub extern "C" fn praeda_item_array_get(
@@ -732,10 +962,105 @@ pub extern "C" fn praeda_item_array_count(handle: *const CItemArrayHandle) -> c_
     unsafe { (*handle).array.count }
 }
 
+/// Check if an item has a prefix affix
+/// Returns 1 if the prefix is set, 0 if not (empty name), -1 on error
+#[unsafe(no_mangle)]
+pub extern "C" fn praeda_item_has_prefix(item: *const CItem) -> i32 {
+    if item.is_null() {
+        return -1;
+    }
+
+    let item = unsafe { &*item };
+    if item.prefix.is_present() { 1 } else { 0 }
+}
+
+/// Check if an item has a suffix affix
+/// Returns 1 if the suffix is set, 0 if not (empty name), -1 on error
+#[unsafe(no_mangle)]
+pub extern "C" fn praeda_item_has_suffix(item: *const CItem) -> i32 {
+    if item.is_null() {
+        return -1;
+    }
+
+    let item = unsafe { &*item };
+    if item.suffix.is_present() { 1 } else { 0 }
+}
+
+/// Look up an item attribute by name
+/// Returns 0 and copies the attribute into out if found, -1 if not found or on error
+/// out->name borrows the item's string: it stays valid until the item is freed and must not
+/// be freed separately
+#[unsafe(no_mangle)]
+pub extern "C" fn praeda_item_get_attribute(
+    item: *const CItem,
+    name: *const c_char,
+    out: *mut CItemAttribute,
+) -> i32 {
+    if item.is_null() || name.is_null() || out.is_null() {
+        return -1;
+    }
+
+    let item = unsafe { &*item };
+    if item.attributes.is_null() {
+        return -1;
+    }
+    let name = unsafe { CStr::from_ptr(name) };
+    let attributes = unsafe { std::slice::from_raw_parts(item.attributes, item.attributes_count as usize) };
+    let Some(attr) = attributes
+        .iter()
+        .find(|attr| !attr.name.is_null() && unsafe { CStr::from_ptr(attr.name) } == name)
+    else {
+        return -1;
+    };
+
+    unsafe {
+        *out = CItemAttribute {
+            name: attr.name,
+            initial_value: attr.initial_value,
+            min: attr.min,
+            max: attr.max,
+            required: attr.required,
+            scaling_factor: attr.scaling_factor,
+            chance: attr.chance,
+            format: attr.format,
+        };
+    }
+    0
+}
+
 // ============================================================================
 // Query Methods
 // ============================================================================
 
+/// Check the generator configuration for common mistakes
+/// Returns 0 if no issues are found, the number of issues otherwise, -1 on error
+/// Writes a JSON array of issue messages to out_json; free it with praeda_string_free()
+#[unsafe(no_mangle)]
+pub extern "C" fn praeda_generator_validate(
+    handle: *const PraedaGeneratorHandle,
+    out_json: *mut *mut c_char,
+) -> i32 {
+    if handle.is_null() {
+        return -1;
+    }
+
+    let generator = unsafe { &(*handle).generator };
+    let issues = generator.validation_issues();
+    if !out_json.is_null() {
+        let json = match serde_json::to_string(&issues) {
+            Ok(json) => json,
+            Err(_) => return -1,
+        };
+        match CString::new(json) {
+            Ok(json) => unsafe {
+                *out_json = json.into_raw();
+            },
+            Err(_) => return -1,
+        }
+    }
+    issues.len() as i32
+}
+
 /// Check if a quality exists
 /// Returns 1 if exists, 0 if not, -1 on error
 #[unsafe(no_mangle)]
@@ -761,6 +1086,17 @@ pub extern "C" fn praeda_generator_has_quality(
     }
 }
 
+/// Returns the error code of the last load or generate call on this thread
+/// 0 if it succeeded, otherwise one of the PRAEDA_ERROR_* codes
+///
+/// Only `praeda_generator_load_toml`, `praeda_generator_generate_loot`,
+/// `praeda_generator_generate_loot_json_pretty` and `praeda_generator_generate_into` set the
+/// code; setters and queries leave it unchanged.
+#[unsafe(no_mangle)]
+pub extern "C" fn praeda_last_error_code() -> i32 {
+    LAST_ERROR_CODE.with(|last| last.get())
+}
+
 /// Returns the version string as a C string pointer
 /// Caller must free returned string with praeda_string_free()
 #[unsafe(no_mangle)]
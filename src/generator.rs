@@ -1,9 +1,104 @@
 use crate::error::{PraedaError, Result};
 use crate::models::*;
-use rand::Rng;
-use std::collections::HashMap;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 
+/// RNG used for every roll made while generating an item
+type LootRng = StdRng;
+
+/// Wraps an RNG and counts how many times it is drawn from, for [`GenerationReport`]
+struct CountingRng<R> {
+    inner: R,
+    calls: u64,
+}
+
+impl<R: RngCore> RngCore for CountingRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.calls += 1;
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.calls += 1;
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.calls += 1;
+        self.inner.fill_bytes(dst)
+    }
+}
+
+/// Serializable copy of a generator's configuration, used for the bincode format.
+///
+/// Metadata values are stored as JSON text since bincode cannot decode self-describing values.
+#[cfg(feature = "bincode")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct GeneratorSnapshot {
+    quality_data: HashMap<String, i32>,
+    quality_data_f64: HashMap<String, f64>,
+    item_types: Vec<ItemType>,
+    item_list: HashMap<(String, String), Vec<String>>,
+    quality_item_list: HashMap<(String, String, String), Vec<String>>,
+    localized_item_list: HashMap<(String, String, String), Vec<String>>,
+    item_attributes: HashMap<(String, String), Vec<ItemAttribute>>,
+    attribute_overrides: HashMap<(String, String), HashSet<String>>,
+    quality_attributes: HashMap<(String, String, String), Vec<ItemAttribute>>,
+    item_affixes: HashMap<(String, String), (Vec<Affix>, Vec<Affix>)>,
+    subtype_metadata: HashMap<(String, String), HashMap<String, String>>,
+    item_name_metadata: HashMap<(String, String, String), HashMap<String, String>>,
+    quality_metadata: HashMap<String, HashMap<String, String>>,
+    /// Affix metadata keyed by (item_type, subtype, is_prefix, affix_name), stored apart from
+    /// `item_affixes` as JSON strings like the other metadata
+    affix_metadata: HashMap<(String, String, bool, String), HashMap<String, String>>,
+    quality_min_affixes: HashMap<String, u32>,
+    quality_ranks: HashMap<String, u32>,
+    quality_affix_tiers: HashMap<String, u32>,
+    subtype_level_offsets: HashMap<(String, String), f64>,
+    quality_level_bonuses: HashMap<String, f64>,
+    affix_slots: HashMap<String, (bool, bool)>,
+    empty_drop_weight: i32,
+    affix_count_distribution: Vec<(u32, i32)>,
+    attribute_templates: HashMap<String, Vec<ItemAttribute>>,
+    attribute_groups: HashMap<(String, String), Vec<AttributeGroup>>,
+    max_items: u32,
+    store_loot: bool,
+    require_names: bool,
+}
+
+/// Source name recorded in an item's attribute breakdown for non-affix contributions.
+///
+/// Wrapped in angle brackets so it cannot be mistaken for an affix named "base".
+pub const BASE_ATTRIBUTE_SOURCE: &str = "<base>";
+
+/// Source name recorded in an item's attribute breakdown when a value is clamped to its bounds.
+///
+/// Wrapped in angle brackets so it cannot be mistaken for an affix named "clamp".
+pub const CLAMP_ATTRIBUTE_SOURCE: &str = "<clamp>";
+
+/// Default cap on `number_of_items` for a single generation call
+pub const DEFAULT_MAX_ITEMS: u32 = 10_000_000;
+
+/// Names not yet drawn in the current batch when `unique_names_within_batch` is set
+#[derive(Default)]
+struct NameDeck {
+    /// Remaining names keyed by (item_type, subtype, name pool). The pool is the locale or
+    /// quality the names came from, or empty for the general names.
+    pools: HashMap<(String, String, String), Vec<String>>,
+    /// Times an exhausted pool was refilled, so its names started repeating
+    refills: u64,
+}
+
+impl NameDeck {
+    fn new() -> Self {
+        Self::default()
+    }
+}
+
 /// The main procedural loot generator.
 ///
 /// `PraedaGenerator` creates randomized game items with qualities, types, affixes, and attributes.
@@ -12,16 +107,17 @@ use std::fs;
 ///
 /// # Configuration Methods
 ///
-/// - Qualities: [`set_quality_data`](Self::set_quality_data), [`has_quality`](Self::has_quality)
+/// - Qualities: [`set_quality_data`](Self::set_quality_data), [`set_quality_data_f64`](Self::set_quality_data_f64), [`has_quality`](Self::has_quality)
 /// - Item Types: [`set_item_type`](Self::set_item_type), [`set_item_subtype`](Self::set_item_subtype)
-/// - Item Names: [`set_item`](Self::set_item)
-/// - Attributes: [`set_attribute`](Self::set_attribute)
+/// - Item Names: [`set_item`](Self::set_item), [`set_item_for_quality`](Self::set_item_for_quality)
+/// - Attributes: [`set_attribute`](Self::set_attribute), [`set_quality_attribute`](Self::set_quality_attribute)
 /// - Affixes (prefixes/suffixes): [`set_affix`](Self::set_affix), [`set_affix_attribute`](Self::set_affix_attribute)
 ///
 /// # Generation
 ///
 /// - [`generate_loot`](Self::generate_loot) - Generate items and return as `Vec<Item>`
 /// - [`generate_loot_json`](Self::generate_loot_json) - Generate items and return as JSON string
+/// - [`generate_loot_with_hook`](Self::generate_loot_with_hook) - Generate items, mutating each with a callback
 ///
 /// # Example
 ///
@@ -47,6 +143,7 @@ use std::fs;
 ///     affix_chance: 0.25,
 ///     linear: true,
 ///     scaling_factor: 1.5,
+///     ..Default::default()
 /// };
 ///
 /// let items = generator.generate_loot(&options, &Default::default(), "loot_key")?;
@@ -55,16 +152,58 @@ use std::fs;
 /// }
 /// # Ok::<_, Box<dyn std::error::Error>>(())
 /// ```
+#[derive(Clone)]
 pub struct PraedaGenerator {
     quality_data: HashMap<String, i32>,
+    /// Fractional quality weights; used instead of `quality_data` and never mixed with it
+    quality_data_f64: HashMap<String, f64>,
     item_types: Vec<ItemType>,
     item_list: HashMap<(String, String), Vec<String>>,
+    /// Quality-specific item names: (item_type, subtype, quality) -> names
+    quality_item_list: HashMap<(String, String, String), Vec<String>>,
+    /// Localized item names: (item_type, subtype, locale) -> names
+    localized_item_list: HashMap<(String, String, String), Vec<String>>,
     item_attributes: HashMap<(String, String), Vec<ItemAttribute>>,
+    /// Attribute names per type/subtype that replace inherited attributes instead of stacking
+    attribute_overrides: HashMap<(String, String), HashSet<String>>,
+    /// Quality-exclusive attributes: (quality, item_type, subtype) -> attributes
+    quality_attributes: HashMap<(String, String, String), Vec<ItemAttribute>>,
     item_affixes: HashMap<(String, String), (Vec<Affix>, Vec<Affix>)>,
     subtype_metadata: HashMap<(String, String), HashMap<String, serde_json::Value>>,
     /// Per-item metadata: (item_type, subtype, item_name) -> metadata map
     item_name_metadata: HashMap<(String, String, String), HashMap<String, serde_json::Value>>,
+    /// Per-quality metadata, e.g. a display color
+    quality_metadata: HashMap<String, HashMap<String, serde_json::Value>>,
     loot_list: HashMap<String, Vec<Item>>,
+    /// Seed used by the last storing generation call, drawn from entropy when unseeded
+    last_seed: Option<u64>,
+    name_fallback: NameFallback,
+    /// Fail instead of using `name_fallback` when a type/subtype has no names
+    require_names: bool,
+    /// Minimum number of affixes (0-2) forced onto items of a quality
+    quality_min_affixes: HashMap<String, u32>,
+    /// Rarity rank per quality, lowest most common; independent of drop weight
+    quality_ranks: HashMap<String, u32>,
+    /// Highest affix tier each quality unlocks; qualities not listed only allow tier 0
+    quality_affix_tiers: HashMap<String, u32>,
+    /// Level offset added to generated items of a (type, subtype)
+    subtype_level_offsets: HashMap<(String, String), f64>,
+    /// Level bonus added to generated items of a quality
+    quality_level_bonuses: HashMap<String, f64>,
+    /// Per-type (allow_prefix, allow_suffix) slot rules; types not listed allow both
+    affix_slots: HashMap<String, (bool, bool)>,
+    /// Weight of rolling no item at all, relative to the quality weights
+    empty_drop_weight: i32,
+    /// Weighted (affix count, weight) pairs; empty means per-slot affix chance rolls
+    affix_count_distribution: Vec<(u32, i32)>,
+    /// Named attribute sets that can be copied onto type/subtypes
+    attribute_templates: HashMap<String, Vec<ItemAttribute>>,
+    /// Pick-N-of-M attribute groups per type/subtype, rolled in insertion order
+    attribute_groups: HashMap<(String, String), Vec<AttributeGroup>>,
+    /// Largest `number_of_items` a single generation call accepts
+    max_items: u32,
+    /// Whether generated batches are kept in `loot_list` under their key
+    store_loot: bool,
 }
 
 impl PraedaGenerator {
@@ -84,13 +223,34 @@ impl PraedaGenerator {
     pub fn new() -> Self {
         PraedaGenerator {
             quality_data: HashMap::new(),
+            quality_data_f64: HashMap::new(),
             item_types: Vec::new(),
             item_list: HashMap::new(),
+            quality_item_list: HashMap::new(),
+            localized_item_list: HashMap::new(),
             item_attributes: HashMap::new(),
+            attribute_overrides: HashMap::new(),
+            quality_attributes: HashMap::new(),
             item_affixes: HashMap::new(),
             subtype_metadata: HashMap::new(),
             item_name_metadata: HashMap::new(),
+            quality_metadata: HashMap::new(),
             loot_list: HashMap::new(),
+            last_seed: None,
+            name_fallback: NameFallback::default(),
+            require_names: false,
+            quality_min_affixes: HashMap::new(),
+            quality_ranks: HashMap::new(),
+            quality_affix_tiers: HashMap::new(),
+            subtype_level_offsets: HashMap::new(),
+            quality_level_bonuses: HashMap::new(),
+            affix_slots: HashMap::new(),
+            empty_drop_weight: 0,
+            affix_count_distribution: Vec::new(),
+            attribute_templates: HashMap::new(),
+            attribute_groups: HashMap::new(),
+            max_items: DEFAULT_MAX_ITEMS,
+            store_loot: true,
         }
     }
 
@@ -122,12 +282,144 @@ impl PraedaGenerator {
         &self.quality_data
     }
 
+    /// Sets a quality tier with a fractional weight.
+    ///
+    /// Lets designers express precise ratios directly, e.g. a 0.5% legendary drop alongside a
+    /// 99.5% common one. Float weights cannot be mixed with integer weights from
+    /// [`set_quality_data`](Self::set_quality_data): generation fails with
+    /// [`PraedaError::InvalidData`] if both kinds are configured.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_quality_data_f64("common", 99.5);
+    /// generator.set_quality_data_f64("legendary", 0.5);
+    /// ```
+    pub fn set_quality_data_f64(&mut self, quality: &str, weight: f64) {
+        self.quality_data_f64.insert(quality.to_string(), weight);
+    }
+
+    /// Get all fractional quality weights
+    pub fn get_quality_data_f64(&self) -> &HashMap<String, f64> {
+        &self.quality_data_f64
+    }
+
     /// Check if a quality exists
     pub fn has_quality(&self, quality: &str) -> bool {
         if quality.is_empty() {
             return true;
         }
-        self.quality_data.contains_key(quality)
+        self.quality_data.contains_key(quality) || self.quality_data_f64.contains_key(quality)
+    }
+
+    /// Sets the weight of a "no drop" result.
+    ///
+    /// Before each item is generated, an empty drop competes with the total quality weight:
+    /// with qualities weighing 100 in total and an empty drop weight of 25, one roll in five
+    /// produces nothing. Generation can therefore return fewer items than
+    /// `options.number_of_items`. A weight of 0 (the default) disables empty drops.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_quality_data("common", 100);
+    /// generator.set_empty_drop_weight(25); // 20% of chests are empty
+    /// ```
+    pub fn set_empty_drop_weight(&mut self, weight: i32) {
+        self.empty_drop_weight = weight;
+    }
+
+    /// Get the weight of a "no drop" result
+    pub fn get_empty_drop_weight(&self) -> i32 {
+        self.empty_drop_weight
+    }
+
+    /// Sets the largest `number_of_items` a single generation call accepts.
+    ///
+    /// Requests above the cap fail with [`PraedaError::InvalidData`] instead of attempting a
+    /// huge allocation, which matters when the count comes from an FFI caller. Defaults to
+    /// [`DEFAULT_MAX_ITEMS`]; use `u32::MAX` to disable the cap.
+    pub fn set_max_items(&mut self, max_items: u32) {
+        self.max_items = max_items;
+    }
+
+    /// Get the largest `number_of_items` a single generation call accepts
+    pub fn get_max_items(&self) -> u32 {
+        self.max_items
+    }
+
+    /// Sets whether generated loot is stored under its key (enabled by default).
+    ///
+    /// Long-running servers that only use the returned items can disable storage so the loot
+    /// list never grows. While disabled, generation calls skip storing entirely and
+    /// [`get_loot`](Self::get_loot) returns nothing for new keys. Loot stored before disabling
+    /// is kept.
+    pub fn set_store_loot(&mut self, store_loot: bool) {
+        self.store_loot = store_loot;
+    }
+
+    /// Whether generated loot is stored under its key
+    pub fn get_store_loot(&self) -> bool {
+        self.store_loot
+    }
+
+    /// Sets the minimum number of affixes items of a quality must have.
+    ///
+    /// Items only have a prefix and a suffix slot, so values above 2 behave like 2. When the
+    /// per-slot affix roll leaves an item below the minimum, empty slots are filled (prefix
+    /// first). Generation fails with [`PraedaError::InvalidData`] if the item's type/subtype
+    /// does not have enough affixes configured to meet the minimum.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Legendary items always have at least one affix
+    /// generator.set_quality_min_affixes("legendary", 1);
+    /// ```
+    pub fn set_quality_min_affixes(&mut self, quality: &str, min_affixes: u32) {
+        self.quality_min_affixes.insert(quality.to_string(), min_affixes);
+    }
+
+    /// Get the minimum number of affixes for a quality (0 if not set)
+    pub fn get_quality_min_affixes(&self, quality: &str) -> u32 {
+        self.quality_min_affixes.get(quality).copied().unwrap_or(0)
+    }
+
+    /// Sets the rarity rank of a quality, independent of its drop weight.
+    ///
+    /// Lower ranks are more common, so "common" might be 0 and "legendary" 4. Ranks are used for
+    /// ordering, e.g. by [`qualities_sorted_by_rank`](Self::qualities_sorted_by_rank) for UI
+    /// sorting, and do not affect how often a quality drops.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_quality_rank("common", 0);
+    /// generator.set_quality_rank("rare", 2);
+    /// ```
+    pub fn set_quality_rank(&mut self, quality: &str, rank: u32) {
+        self.quality_ranks.insert(quality.to_string(), rank);
+    }
+
+    /// Get the rarity rank of a quality, if one is set
+    pub fn get_quality_rank(&self, quality: &str) -> Option<u32> {
+        self.quality_ranks.get(quality).copied()
+    }
+
+    /// Get the configured qualities ordered by rank, lowest first
+    ///
+    /// Qualities without a rank come last, ordered by name.
+    pub fn qualities_sorted_by_rank(&self) -> Vec<String> {
+        let mut qualities: Vec<String> = self
+            .quality_data
+            .keys()
+            .chain(self.quality_data_f64.keys())
+            .cloned()
+            .collect();
+        qualities.sort();
+        qualities.dedup();
+        qualities.sort_by_key(|quality| self.get_quality_rank(quality).unwrap_or(u32::MAX));
+        qualities
     }
 
     /// Adds or updates an item type with a relative weight.
@@ -236,6 +528,53 @@ impl PraedaGenerator {
         }
     }
 
+    /// Get the subtype weights for a specific item type, keyed by subtype name
+    ///
+    /// Returns an empty map if the type is not configured.
+    pub fn get_subtype_weights(&self, item_type: &str) -> HashMap<String, i32> {
+        self.get_item_type(item_type)
+            .map(|type_obj| type_obj.get_subtypes().clone())
+            .unwrap_or_default()
+    }
+
+    /// Sets a level offset for items of a type/subtype.
+    ///
+    /// The offset is added to each generated item level of that subtype, before attributes are
+    /// scaled and before [`GeneratorOptions::max_level`] is applied. Offsets can be negative,
+    /// e.g. to make rings read a few levels below the weapons in the same drop.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_subtype_level_offset("accessory", "ring", -5.0);
+    /// ```
+    pub fn set_subtype_level_offset(&mut self, item_type: &str, subtype: &str, offset: f64) {
+        self.subtype_level_offsets
+            .insert((item_type.to_string(), subtype.to_string()), offset);
+    }
+
+    /// Get the level offset for a type/subtype (0.0 if not set)
+    pub fn get_subtype_level_offset(&self, item_type: &str, subtype: &str) -> f64 {
+        self.subtype_level_offsets
+            .get(&(item_type.to_string(), subtype.to_string()))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Sets a level bonus for items of a quality, e.g. `+5.0` for "legendary".
+    ///
+    /// Like [`set_subtype_level_offset`](Self::set_subtype_level_offset), the bonus is added to
+    /// the rolled item level before attributes are scaled and before
+    /// [`GeneratorOptions::max_level`] is applied, so rarer items get stronger through level.
+    pub fn set_quality_level_bonus(&mut self, quality: &str, bonus: f64) {
+        self.quality_level_bonuses.insert(quality.to_string(), bonus);
+    }
+
+    /// Get the level bonus for a quality (0.0 if not set)
+    pub fn get_quality_level_bonus(&self, quality: &str) -> f64 {
+        self.quality_level_bonuses.get(quality).copied().unwrap_or(0.0)
+    }
+
     /// Get all weapon subtypes (convenience method for "Weapon" type)
     pub fn get_weapon_subtypes(&self) -> Vec<String> {
         self.get_subtypes_for_type("Weapon")
@@ -247,11 +586,67 @@ impl PraedaGenerator {
         self.item_list.get(&key).cloned().unwrap_or_default()
     }
 
+    /// Check whether a name is in the item names for a type/subtype, without cloning the list
+    ///
+    /// Like [`get_item_names`](Self::get_item_names), this only looks at the general names, not
+    /// quality-specific or localized ones.
+    pub fn has_item_name(&self, item_type: &str, subtype: &str, name: &str) -> bool {
+        let key = (item_type.to_string(), subtype.to_string());
+        self.item_list
+            .get(&key)
+            .is_some_and(|names| names.iter().any(|n| n == name))
+    }
+
     /// Get all item types as a list of strings
     pub fn get_item_type_names(&self) -> Vec<String> {
         self.item_types.iter().map(|it| it.item_type.clone()).collect()
     }
 
+    /// Sets the highest affix tier a quality unlocks.
+    ///
+    /// An affix of tier `n` (see [`Affix::tier`]) only rolls on items whose quality has been
+    /// given a tier of at least `n`. Tier 0 affixes roll on every quality, and qualities that
+    /// were never set only get tier 0 affixes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Tier 2 affixes only appear on epic and legendary items
+    /// generator.set_affix_tier("weapon", "", true, "Godslayer", 2);
+    /// generator.set_quality_affix_tier_min("epic", 2);
+    /// generator.set_quality_affix_tier_min("legendary", 2);
+    /// ```
+    pub fn set_quality_affix_tier_min(&mut self, quality: &str, min_tier: u32) {
+        self.quality_affix_tiers.insert(quality.to_string(), min_tier);
+    }
+
+    /// Get the highest affix tier a quality unlocks (0 if not set)
+    pub fn get_quality_affix_tier(&self, quality: &str) -> u32 {
+        self.quality_affix_tiers.get(quality).copied().unwrap_or(0)
+    }
+
+    /// Set metadata for a specific quality
+    ///
+    /// Items of that quality carry the metadata, e.g. `color = "#0070dd"` for "rare", so
+    /// consumers do not need their own quality-to-color map. Subtype, item name and affix
+    /// metadata take precedence over quality metadata with the same key.
+    pub fn set_quality_metadata(&mut self, quality: &str, key: &str, value: serde_json::Value) {
+        self.quality_metadata
+            .entry(quality.to_string())
+            .or_default()
+            .insert(key.to_string(), value);
+    }
+
+    /// Get metadata for a specific quality
+    pub fn get_quality_metadata(&self, quality: &str, key: &str) -> Option<&serde_json::Value> {
+        self.quality_metadata.get(quality).and_then(|m| m.get(key))
+    }
+
+    /// Get all metadata for a specific quality
+    pub fn get_all_quality_metadata(&self, quality: &str) -> Option<&HashMap<String, serde_json::Value>> {
+        self.quality_metadata.get(quality)
+    }
+
     /// Set metadata for a specific subtype
     pub fn set_subtype_metadata(
         &mut self,
@@ -329,12 +724,363 @@ impl PraedaGenerator {
         self.item_name_metadata.get(&type_key)
     }
 
+    /// Get the metadata keys set for a specific item name, sorted (empty if none)
+    pub fn get_item_name_metadata_keys(&self, item_type: &str, subtype: &str, item_name: &str) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .get_all_item_name_metadata(item_type, subtype, item_name)
+            .map(|metadata| metadata.keys().cloned().collect())
+            .unwrap_or_default();
+        keys.sort();
+        keys
+    }
+
+    /// Iterate over all item-name metadata as `((item_type, subtype, item_name), metadata)`,
+    /// in no particular order
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// for ((item_type, subtype, name), metadata) in generator.iter_item_name_metadata() {
+    ///     println!("{}/{}/{}: {:?}", item_type, subtype, name, metadata);
+    /// }
+    /// ```
+    pub fn iter_item_name_metadata(
+        &self,
+    ) -> impl Iterator<Item = ((&str, &str, &str), &HashMap<String, serde_json::Value>)> {
+        self.item_name_metadata.iter().map(|((item_type, subtype, name), metadata)| {
+            ((item_type.as_str(), subtype.as_str(), name.as_str()), metadata)
+        })
+    }
+
+    /// Counts the distinct items the current configuration can produce.
+    ///
+    /// An item is identified by its quality, type, subtype and name. Qualities, types and
+    /// subtypes with a weight of zero or less are never rolled and are not counted. A
+    /// type/subtype without configured names contributes one (fallback) name per quality.
+    ///
+    /// Use this to check that a request for N distinct items is feasible.
+    pub fn count_possible_items(&self) -> usize {
+        self.count_possible_items_by(|_, _| 1)
+    }
+
+    /// Like [`count_possible_items`](Self::count_possible_items), but also counts each
+    /// combination of prefix and suffix (including no prefix and no suffix) as a distinct item.
+    pub fn count_possible_items_with_affixes(&self) -> usize {
+        self.count_possible_items_by(|item_type, subtype| {
+            let (prefixes, suffixes) = self.affix_pools(item_type, subtype, false, None, None);
+            (prefixes.len() + 1) * (suffixes.len() + 1)
+        })
+    }
+
+    /// Whether an item of the given type, subtype and quality can ever be generated with random
+    /// selection under the current configuration.
+    ///
+    /// Checks that the quality, type and subtype all have a positive weight, that the
+    /// type/subtype has item names or attributes configured, and that enough affixes exist to
+    /// meet the quality's minimum (see [`set_quality_min_affixes`](Self::set_quality_min_affixes)).
+    /// Useful for flagging configured items that will never drop.
+    pub fn can_generate(&self, item_type: &str, subtype: &str, quality: &str) -> bool {
+        let quality_weighted = self.quality_data.get(quality).is_some_and(|w| *w > 0)
+            || self.quality_data_f64.get(quality).is_some_and(|w| *w > 0.0);
+        let subtype_weighted = self.get_item_type(item_type).is_some_and(|it| {
+            it.weight > 0 && it.subtypes.get(subtype).is_some_and(|w| *w > 0)
+        });
+        if !quality_weighted || !subtype_weighted {
+            return false;
+        }
+
+        let has_names = !self.get_item_names_for_quality(item_type, subtype, quality).is_empty()
+            || !self.get_item_names(item_type, subtype).is_empty();
+        let has_attributes = [
+            ("", ""),
+            (item_type, ""),
+            ("", subtype),
+            (item_type, subtype),
+        ]
+        .iter()
+        .any(|(t, s)| {
+            self.item_attributes
+                .get(&(t.to_string(), s.to_string()))
+                .is_some_and(|attributes| !attributes.is_empty())
+        });
+        if !has_names && !has_attributes {
+            return false;
+        }
+
+        let (prefixes, suffixes) = self.affix_pools(item_type, subtype, false, None, Some(quality));
+        let available_affixes = (!prefixes.is_empty()) as u32 + (!suffixes.is_empty()) as u32;
+        self.get_quality_min_affixes(quality).min(2) <= available_affixes
+    }
+
+    /// Checks the configuration for common mistakes before generating.
+    ///
+    /// See [`validation_issues`](Self::validation_issues) for what is checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PraedaError::InvalidData`] listing every issue found.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.load_data_from_file("loot_config.toml")?;
+    /// generator.validate()?;
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        let issues = self.validation_issues();
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(PraedaError::InvalidData(issues.join("; ")))
+        }
+    }
+
+    /// Lists configuration mistakes, one message per issue; empty if none are found.
+    ///
+    /// Flags a configuration with no qualities or no item types, and every reachable
+    /// type/subtype (both with a positive weight) that has no required attributes, whether set
+    /// on the type/subtype itself or inherited from the global, type-wide or subtype-wide
+    /// attributes. Items of such a subtype would only carry the synthetic level attribute,
+    /// which usually means an attribute was forgotten.
+    pub fn validation_issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+        if self.quality_data.is_empty() && self.quality_data_f64.is_empty() {
+            issues.push("No qualities configured".to_string());
+        }
+        if self.item_types.is_empty() {
+            issues.push("No item types configured".to_string());
+        }
+
+        let mut missing: Vec<String> = Vec::new();
+        for item_type in self.item_types.iter().filter(|it| it.weight > 0) {
+            for (subtype, _) in item_type.subtypes.iter().filter(|(_, weight)| **weight > 0) {
+                let has_required = [
+                    ("", ""),
+                    (item_type.item_type.as_str(), ""),
+                    ("", subtype.as_str()),
+                    (item_type.item_type.as_str(), subtype.as_str()),
+                ]
+                .iter()
+                .any(|(t, s)| {
+                    self.item_attributes
+                        .get(&(t.to_string(), s.to_string()))
+                        .is_some_and(|attributes| attributes.iter().any(|a| a.required))
+                });
+                if !has_required {
+                    missing.push(format!("{}/{}", item_type.item_type, subtype));
+                }
+            }
+        }
+        missing.sort();
+        issues.extend(
+            missing
+                .into_iter()
+                .map(|key| format!("No required attributes configured for {}", key)),
+        );
+
+        issues
+    }
+
+    /// Whether two generators have the same configuration, ignoring stored loot and the last
+    /// seed.
+    ///
+    /// Useful for checking configuration round-trips, e.g. TOML to generator and back. Custom
+    /// name fallbacks are only equal when they share the same closure.
+    pub fn config_eq(&self, other: &Self) -> bool {
+        // Destructured so a new field cannot be left out of the comparison by accident
+        let PraedaGenerator {
+            quality_data,
+            quality_data_f64,
+            item_types,
+            item_list,
+            quality_item_list,
+            localized_item_list,
+            item_attributes,
+            attribute_overrides,
+            quality_attributes,
+            item_affixes,
+            subtype_metadata,
+            item_name_metadata,
+            quality_metadata,
+            loot_list: _,
+            last_seed: _,
+            name_fallback,
+            require_names,
+            quality_min_affixes,
+            quality_ranks,
+            quality_affix_tiers,
+            subtype_level_offsets,
+            quality_level_bonuses,
+            affix_slots,
+            empty_drop_weight,
+            affix_count_distribution,
+            attribute_templates,
+            attribute_groups,
+            max_items,
+            store_loot,
+        } = self;
+
+        let same_fallback = match (name_fallback, &other.name_fallback) {
+            (NameFallback::Subtype, NameFallback::Subtype)
+            | (NameFallback::SubtypeWithType, NameFallback::SubtypeWithType) => true,
+            (NameFallback::Custom(a), NameFallback::Custom(b)) => std::sync::Arc::ptr_eq(a, b),
+            _ => false,
+        };
+
+        // Item types are a list, but their order does not affect generation
+        let mut types: Vec<&ItemType> = item_types.iter().collect();
+        let mut other_types: Vec<&ItemType> = other.item_types.iter().collect();
+        types.sort_by(|a, b| a.item_type.cmp(&b.item_type));
+        other_types.sort_by(|a, b| a.item_type.cmp(&b.item_type));
+
+        same_fallback
+            && types == other_types
+            && *quality_data == other.quality_data
+            && *quality_data_f64 == other.quality_data_f64
+            && *item_list == other.item_list
+            && *quality_item_list == other.quality_item_list
+            && *localized_item_list == other.localized_item_list
+            && *item_attributes == other.item_attributes
+            && *attribute_overrides == other.attribute_overrides
+            && *quality_attributes == other.quality_attributes
+            && *item_affixes == other.item_affixes
+            && *subtype_metadata == other.subtype_metadata
+            && *item_name_metadata == other.item_name_metadata
+            && *quality_metadata == other.quality_metadata
+            && *require_names == other.require_names
+            && *quality_min_affixes == other.quality_min_affixes
+            && *quality_ranks == other.quality_ranks
+            && *quality_affix_tiers == other.quality_affix_tiers
+            && *subtype_level_offsets == other.subtype_level_offsets
+            && *quality_level_bonuses == other.quality_level_bonuses
+            && *affix_slots == other.affix_slots
+            && *empty_drop_weight == other.empty_drop_weight
+            && *affix_count_distribution == other.affix_count_distribution
+            && *attribute_templates == other.attribute_templates
+            && *attribute_groups == other.attribute_groups
+            && *max_items == other.max_items
+            && *store_loot == other.store_loot
+    }
+
+    /// Finds attribute values outside the bounds they were configured with, as
+    /// `(item_name, attr_name, value)` tuples in item order.
+    ///
+    /// A QA tool for checking generated loot against the configuration. Each attribute is
+    /// checked against the most specific configured attribute of that name for the item's
+    /// quality, type and subtype, falling back to the item's prefix and suffix. Attributes
+    /// configured without a usable range (see [`ItemAttribute::has_bounds`]) or not configured
+    /// at all, like the level attribute, are skipped. Per-level bounds are evaluated at the
+    /// item's level attribute (see [`Item::get_level_attribute`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let items = generator.generate_loot(&options, &overrides, "qa")?;
+    /// for (item, attr, value) in generator.verify_attribute_bounds(&items) {
+    ///     eprintln!("{}: {} = {} is out of bounds", item, attr, value);
+    /// }
+    /// ```
+    pub fn verify_attribute_bounds(&self, items: &[Item]) -> Vec<(String, String, f64)> {
+        let mut violations = Vec::new();
+        for item in items {
+            let keys = [
+                ("", ""),
+                (item.get_type(), ""),
+                ("", item.get_subtype()),
+                (item.get_type(), item.get_subtype()),
+            ];
+            let configured: Vec<&ItemAttribute> = keys
+                .iter()
+                .flat_map(|(t, s)| {
+                    let key = (t.to_string(), s.to_string());
+                    let quality_key = (item.get_quality().to_string(), key.0.clone(), key.1.clone());
+                    [self.item_attributes.get(&key), self.quality_attributes.get(&quality_key)]
+                })
+                .flatten()
+                .flatten()
+                .collect();
+            let level = item.total_attribute(item.get_level_attribute());
+
+            for (name, attr) in item.get_attributes_ordered() {
+                let Some(bounds) = configured
+                    .iter()
+                    .rev()
+                    .copied()
+                    .find(|a| a.name == name)
+                    .or_else(|| {
+                        [item.get_prefix(), item.get_suffix()]
+                            .into_iter()
+                            .flat_map(|affix| affix.get_attributes())
+                            .find(|a| a.name == name)
+                    })
+                else {
+                    continue;
+                };
+                let min = bounds.min + bounds.min_per_level * level;
+                let max = bounds.max + bounds.max_per_level * level;
+                if min < max && (attr.initial_value < min || attr.initial_value > max) {
+                    violations.push((item.get_name().to_string(), name.to_string(), attr.initial_value));
+                }
+            }
+        }
+        violations
+    }
+
+    fn count_possible_items_by(&self, variants: impl Fn(&str, &str) -> usize) -> usize {
+        let qualities: Vec<&String> = self
+            .quality_data
+            .iter()
+            .filter(|(_, weight)| **weight > 0)
+            .map(|(quality, _)| quality)
+            .chain(
+                self.quality_data_f64
+                    .iter()
+                    .filter(|(_, weight)| **weight > 0.0)
+                    .map(|(quality, _)| quality),
+            )
+            .collect();
+
+        let mut total = 0;
+        for item_type in self.item_types.iter().filter(|it| it.weight > 0) {
+            for (subtype, _) in item_type.subtypes.iter().filter(|(_, weight)| **weight > 0) {
+                let general_names = self
+                    .item_list
+                    .get(&(item_type.item_type.clone(), subtype.clone()))
+                    .map(|names| names.len())
+                    .unwrap_or(0);
+
+                let names: usize = qualities
+                    .iter()
+                    .map(|quality| {
+                        let quality_names = self
+                            .quality_item_list
+                            .get(&(item_type.item_type.clone(), subtype.clone(), quality.to_string()))
+                            .map(|names| names.len())
+                            .unwrap_or(0);
+                        if quality_names > 0 {
+                            quality_names
+                        } else {
+                            general_names.max(1)
+                        }
+                    })
+                    .sum();
+
+                total += names * variants(&item_type.item_type, subtype);
+            }
+        }
+
+        total
+    }
+
     /// Adds or updates an attribute for a type/subtype.
     ///
     /// Attributes are custom properties on items (e.g., damage, defense, health, mana).
     /// When an item of this type/subtype is generated, all configured attributes are applied to it.
     ///
-    /// If an attribute with the same name already exists, its initial value is added to the existing value.
+    /// This is additive: if an attribute with the same name already exists, only its initial
+    /// value changes, by adding the new initial value to it. Calling this twice with a damage of
+    /// 10.0 leaves a damage of 20.0, and the bounds and other settings of the first call are kept.
+    /// Use [`replace_attribute`](Self::replace_attribute) to overwrite an attribute instead.
     ///
     /// # Arguments
     ///
@@ -372,58 +1118,348 @@ impl PraedaGenerator {
         }
     }
 
-    /// Check if attribute exists
-    pub fn has_attribute(&self, type_name: &str, subtype: &str, attr_name: &str) -> bool {
-        if !self.has_item_type(type_name) || !self.has_item_subtype(type_name, subtype) {
-            return false;
-        }
-
+    /// Adds an attribute for a type/subtype, overwriting any existing attribute with the same name.
+    ///
+    /// Unlike [`set_attribute`](Self::set_attribute), which adds to the existing initial value,
+    /// the whole attribute is replaced, including its bounds and settings.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_attribute("weapon", "", ItemAttribute::new("damage", 10.0, 5.0, 20.0, true));
+    /// generator.replace_attribute("weapon", "", ItemAttribute::new("damage", 12.0, 5.0, 25.0, true));
+    /// // damage is now 12.0 with max 25.0
+    /// ```
+    pub fn replace_attribute(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        attribute: ItemAttribute,
+    ) {
         let key = (type_name.to_string(), subtype.to_string());
-        if let Some(attributes) = self.item_attributes.get(&key) {
-            attributes.iter().any(|a| a.name == attr_name)
+        let attributes = self.item_attributes.entry(key).or_default();
+
+        if let Some(pos) = attributes.iter().position(|a| a.name == attribute.name) {
+            attributes[pos] = attribute;
         } else {
-            false
+            attributes.push(attribute);
         }
     }
 
-    /// Sets the possible item names for a type/subtype combination.
-    ///
-    /// When an item of this type/subtype is generated, one name is randomly selected from this list.
-    /// If no names are provided for a type/subtype, the subtype name is used as the item name.
+    /// Sets an attribute for a type/subtype that replaces, rather than adds to, attributes of the
+    /// same name inherited from less specific keys.
     ///
-    /// # Arguments
-    ///
-    /// * `type_name` - The item type
-    /// * `subtype` - The item subtype
-    /// * `names` - Vector of possible item names
+    /// Attributes set on `(type, "")` or `("", "")` normally apply to every subtype and stack
+    /// with a subtype's own attribute of the same name. An override hides those inherited
+    /// attributes for items matching this key, so only its own value is used. Within the key
+    /// it behaves like [`replace_attribute`](Self::replace_attribute).
     ///
     /// # Example
     ///
     /// ```rust,ignore
-    /// generator.set_item("weapon".to_string(), "sword".to_string(), vec![
-    ///     "Iron Sword".to_string(),
-    ///     "Steel Sword".to_string(),
-    ///     "Longsword".to_string(),
-    ///     "Great Sword".to_string(),
-    /// ]);
+    /// generator.set_attribute("weapon", "", ItemAttribute::new("speed", 1.0, 0.0, 0.0, false));
+    /// // Daggers are fast: their speed replaces the weapon-wide speed instead of adding to it
+    /// generator.set_attribute_override("weapon", "dagger", ItemAttribute::new("speed", 2.0, 0.0, 0.0, false));
     /// ```
-    pub fn set_item(&mut self, type_name: &str, subtype: &str, names: Vec<&str>) {
-        let names_owned = names.iter().map(|n| n.to_string()).collect();
-        self.item_list.insert((type_name.to_string(), subtype.to_string()), names_owned);
+    pub fn set_attribute_override(&mut self, type_name: &str, subtype: &str, attribute: ItemAttribute) {
+        self.attribute_overrides
+            .entry((type_name.to_string(), subtype.to_string()))
+            .or_default()
+            .insert(attribute.name.clone());
+        self.replace_attribute(type_name, subtype, attribute);
     }
 
-    /// Adds an attribute to a prefix or suffix affix.
-    ///
-    /// Affixes (prefixes and suffixes) are optional name modifiers that can be added to items.
-    /// Each affix can have multiple attributes that get applied to generated items.
-    ///
-    /// # Arguments
+    /// Check whether an attribute was set with
+    /// [`set_attribute_override`](Self::set_attribute_override) for a type/subtype
+    pub fn is_attribute_override(&self, type_name: &str, subtype: &str, attr_name: &str) -> bool {
+        self.attribute_overrides
+            .get(&(type_name.to_string(), subtype.to_string()))
+            .is_some_and(|names| names.contains(attr_name))
+    }
+
+    /// Adds or updates an attribute that only items of one quality get.
     ///
-    /// * `type_name` - The item type
-    /// * `subtype` - The item subtype
-    /// * `is_prefix` - true for prefix, false for suffix
-    /// * `affix_name` - Name of the affix (e.g., "Flaming", "of Strength")
-    /// * `attribute` - The [`ItemAttribute`] this affix applies
+    /// Works like [`set_attribute`](Self::set_attribute), including the additive behavior for
+    /// repeated names and the `""` wildcards for type and subtype, but the attribute is only
+    /// applied when the rolled quality is `quality`. Use it for rarity-exclusive stats.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_quality_attribute(
+    ///     "legendary", "weapon", "",
+    ///     ItemAttribute::new("legendary_power", 50.0, 0.0, 100.0, true),
+    /// );
+    /// ```
+    pub fn set_quality_attribute(
+        &mut self,
+        quality: &str,
+        type_name: &str,
+        subtype: &str,
+        attribute: ItemAttribute,
+    ) {
+        let key = (quality.to_string(), type_name.to_string(), subtype.to_string());
+        let attributes = self.quality_attributes.entry(key).or_default();
+
+        if let Some(pos) = attributes.iter().position(|a| a.name == attribute.name) {
+            attributes[pos].initial_value += attribute.initial_value;
+        } else {
+            attributes.push(attribute);
+        }
+    }
+
+    /// Defines a named, reusable set of attributes.
+    ///
+    /// Apply it to any number of type/subtypes with
+    /// [`apply_attribute_template`](Self::apply_attribute_template) instead of repeating the
+    /// same [`set_attribute`](Self::set_attribute) calls. Redefining a template replaces it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.define_attribute_template("weapon_base", vec![
+    ///     ItemAttribute::new("damage", 10.0, 5.0, 20.0, true),
+    ///     ItemAttribute::new("attack_speed", 1.0, 0.5, 2.0, true),
+    /// ]);
+    /// generator.apply_attribute_template("weapon", "sword", "weapon_base")?;
+    /// generator.apply_attribute_template("weapon", "axe", "weapon_base")?;
+    /// ```
+    pub fn define_attribute_template(&mut self, name: &str, attributes: Vec<ItemAttribute>) {
+        self.attribute_templates.insert(name.to_string(), attributes);
+    }
+
+    /// Get the attributes of a template, if defined
+    pub fn get_attribute_template(&self, name: &str) -> Option<&[ItemAttribute]> {
+        self.attribute_templates.get(name).map(|attributes| attributes.as_slice())
+    }
+
+    /// Copies a template's attributes onto a type/subtype, as if each were added with
+    /// [`set_attribute`](Self::set_attribute).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PraedaError::InvalidData`] if no template with that name is defined.
+    pub fn apply_attribute_template(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        template_name: &str,
+    ) -> Result<()> {
+        let attributes = self
+            .attribute_templates
+            .get(template_name)
+            .cloned()
+            .ok_or_else(|| {
+                PraedaError::InvalidData(format!("Unknown attribute template: {}", template_name))
+            })?;
+
+        for attribute in attributes {
+            self.set_attribute(type_name, subtype, attribute);
+        }
+        Ok(())
+    }
+
+    /// Defines a group of optional attributes of which exactly `pick` roll on each item of a
+    /// type/subtype.
+    ///
+    /// Models "choose N bonuses" systems: instead of rolling each attribute against the affix
+    /// chance, `pick` distinct attributes are chosen uniformly from the group (all of them if the
+    /// group is smaller) and added to the item. As with [`set_attribute`](Self::set_attribute),
+    /// empty type or subtype names apply the group more broadly. Redefining a group with the same
+    /// name replaces it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_attribute_group("weapon", "sword", "elements", vec![
+    ///     ItemAttribute::new("fire", 5.0, 0.0, 20.0, false),
+    ///     ItemAttribute::new("frost", 5.0, 0.0, 20.0, false),
+    ///     ItemAttribute::new("shock", 5.0, 0.0, 20.0, false),
+    /// ], 1);
+    /// ```
+    pub fn set_attribute_group(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        group_name: &str,
+        attributes: Vec<ItemAttribute>,
+        pick: usize,
+    ) {
+        let key = (type_name.to_string(), subtype.to_string());
+        let groups = self.attribute_groups.entry(key).or_default();
+        let group = AttributeGroup::new(group_name, attributes, pick);
+
+        if let Some(pos) = groups.iter().position(|g| g.name == group_name) {
+            groups[pos] = group;
+        } else {
+            groups.push(group);
+        }
+    }
+
+    /// Get the attribute groups defined for a type/subtype
+    pub fn get_attribute_groups(&self, type_name: &str, subtype: &str) -> &[AttributeGroup] {
+        let key = (type_name.to_string(), subtype.to_string());
+        self.attribute_groups.get(&key).map(|groups| groups.as_slice()).unwrap_or_default()
+    }
+
+    /// Check if attribute exists
+    pub fn has_attribute(&self, type_name: &str, subtype: &str, attr_name: &str) -> bool {
+        if !self.has_item_type(type_name) || !self.has_item_subtype(type_name, subtype) {
+            return false;
+        }
+
+        let key = (type_name.to_string(), subtype.to_string());
+        if let Some(attributes) = self.item_attributes.get(&key) {
+            attributes.iter().any(|a| a.name == attr_name)
+        } else {
+            false
+        }
+    }
+
+    /// Sets the possible item names for a type/subtype combination.
+    ///
+    /// When an item of this type/subtype is generated, one name is randomly selected from this list.
+    /// If no names are provided for a type/subtype, the name comes from the
+    /// [`NameFallback`] strategy (the subtype name by default).
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - The item type
+    /// * `subtype` - The item subtype
+    /// * `names` - Vector of possible item names
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_item("weapon".to_string(), "sword".to_string(), vec![
+    ///     "Iron Sword".to_string(),
+    ///     "Steel Sword".to_string(),
+    ///     "Longsword".to_string(),
+    ///     "Great Sword".to_string(),
+    /// ]);
+    /// ```
+    pub fn set_item(&mut self, type_name: &str, subtype: &str, names: Vec<&str>) {
+        let names_owned = names.iter().map(|n| n.to_string()).collect();
+        self.item_list.insert((type_name.to_string(), subtype.to_string()), names_owned);
+    }
+
+    /// Sets how items are named when no names are configured for their type/subtype.
+    ///
+    /// Defaults to [`NameFallback::Subtype`].
+    pub fn set_name_fallback(&mut self, fallback: NameFallback) {
+        self.name_fallback = fallback;
+    }
+
+    /// Get the current name fallback strategy
+    pub fn get_name_fallback(&self) -> &NameFallback {
+        &self.name_fallback
+    }
+
+    /// Sets whether every generated type/subtype must have item names (disabled by default).
+    ///
+    /// When enabled, generation fails instead of using the [`NameFallback`]:
+    /// with [`PraedaError::MissingItemNames`] if no names were ever set for the type/subtype,
+    /// and with [`PraedaError::EmptyItemNames`] if they were set to an empty list.
+    pub fn set_require_names(&mut self, require_names: bool) {
+        self.require_names = require_names;
+    }
+
+    /// Whether every generated type/subtype must have item names
+    pub fn get_require_names(&self) -> bool {
+        self.require_names
+    }
+
+    /// Sets the possible item names for a type/subtype combination at a specific quality.
+    ///
+    /// When an item of this type/subtype is generated with the given quality, its name is drawn
+    /// from this list instead of the general list set with [`set_item`](Self::set_item).
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - The item type
+    /// * `subtype` - The item subtype
+    /// * `quality` - The quality tier these names are reserved for
+    /// * `names` - Vector of possible item names
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_item_for_quality("weapon", "sword", "legendary", vec!["Excalibur"]);
+    /// ```
+    pub fn set_item_for_quality(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        quality: &str,
+        names: Vec<&str>,
+    ) {
+        let names_owned = names.iter().map(|n| n.to_string()).collect();
+        self.quality_item_list.insert(
+            (type_name.to_string(), subtype.to_string(), quality.to_string()),
+            names_owned,
+        );
+    }
+
+    /// Get quality-specific item names for a type, subtype and quality
+    pub fn get_item_names_for_quality(
+        &self,
+        item_type: &str,
+        subtype: &str,
+        quality: &str,
+    ) -> Vec<String> {
+        let key = (item_type.to_string(), subtype.to_string(), quality.to_string());
+        self.quality_item_list.get(&key).cloned().unwrap_or_default()
+    }
+
+    /// Sets the possible item names for a type/subtype combination in a specific locale.
+    ///
+    /// When generating with [`GeneratorOptions::locale`] set, names are drawn from the
+    /// requested locale's list first, falling back to the quality-specific and general lists
+    /// when the locale has no names for the type/subtype.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_item("weapon", "sword", vec!["longsword"]);
+    /// generator.set_item_localized("weapon", "sword", "fr", vec!["épée longue"]);
+    /// ```
+    pub fn set_item_localized(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        locale: &str,
+        names: Vec<&str>,
+    ) {
+        let names_owned = names.iter().map(|n| n.to_string()).collect();
+        self.localized_item_list.insert(
+            (type_name.to_string(), subtype.to_string(), locale.to_string()),
+            names_owned,
+        );
+    }
+
+    /// Get localized item names for a type, subtype and locale
+    pub fn get_item_names_localized(
+        &self,
+        item_type: &str,
+        subtype: &str,
+        locale: &str,
+    ) -> Vec<String> {
+        let key = (item_type.to_string(), subtype.to_string(), locale.to_string());
+        self.localized_item_list.get(&key).cloned().unwrap_or_default()
+    }
+
+    /// Adds an attribute to a prefix or suffix affix.
+    ///
+    /// Affixes (prefixes and suffixes) are optional name modifiers that can be added to items.
+    /// Each affix can have multiple attributes that get applied to generated items.
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - The item type
+    /// * `subtype` - The item subtype
+    /// * `is_prefix` - true for prefix, false for suffix
+    /// * `affix_name` - Name of the affix (e.g., "Flaming", "of Strength")
+    /// * `attribute` - The [`ItemAttribute`] this affix applies
     ///
     /// # Example
     ///
@@ -478,6 +1514,157 @@ impl PraedaGenerator {
         }
     }
 
+    /// Adds an attribute to an affix, like [`set_affix_attribute`](Self::set_affix_attribute),
+    /// and sets the minimum item level the affix can roll at.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // "of the Dragon" only appears on items of level 40 and up
+    /// generator.set_affix_attribute_with_min_level(
+    ///     "weapon", "", false, "of the Dragon",
+    ///     ItemAttribute::new("fire_damage", 25.0, 0.0, 50.0, true),
+    ///     40.0,
+    /// );
+    /// ```
+    pub fn set_affix_attribute_with_min_level(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        is_prefix: bool,
+        affix_name: &str,
+        attribute: ItemAttribute,
+        min_level: f64,
+    ) {
+        self.set_affix_attribute(type_name, subtype, is_prefix, affix_name, attribute);
+
+        let key = (type_name.to_string(), subtype.to_string());
+        if let Some((prefixes, suffixes)) = self.item_affixes.get_mut(&key) {
+            let affixes = if is_prefix { prefixes } else { suffixes };
+            if let Some(affix) = affixes.iter_mut().find(|a| a.name == affix_name) {
+                affix.set_min_level(min_level);
+            }
+        }
+    }
+
+    /// Sets the rarity tier of an affix, creating the affix if it does not exist yet.
+    ///
+    /// See [`set_quality_affix_tier_min`](Self::set_quality_affix_tier_min) for which qualities
+    /// a tier can roll on.
+    pub fn set_affix_tier(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        is_prefix: bool,
+        affix_name: &str,
+        tier: u32,
+    ) {
+        let key_pair = (type_name.to_string(), subtype.to_string());
+        let (prefixes, suffixes) = self
+            .item_affixes
+            .entry(key_pair)
+            .or_insert((Vec::new(), Vec::new()));
+        let affixes = if is_prefix { prefixes } else { suffixes };
+
+        if let Some(affix) = affixes.iter_mut().find(|a| a.name == affix_name) {
+            affix.set_tier(tier);
+        } else {
+            let mut new_affix = Affix::new(affix_name, Vec::new());
+            new_affix.set_tier(tier);
+            affixes.push(new_affix);
+        }
+    }
+
+    /// Sets the description of an affix, creating the affix if it does not exist yet.
+    ///
+    /// The description is appended to an item's description whenever the affix is applied.
+    pub fn set_affix_description(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        is_prefix: bool,
+        affix_name: &str,
+        description: &str,
+    ) {
+        let key_pair = (type_name.to_string(), subtype.to_string());
+        let (prefixes, suffixes) = self
+            .item_affixes
+            .entry(key_pair)
+            .or_insert((Vec::new(), Vec::new()));
+        let affixes = if is_prefix { prefixes } else { suffixes };
+
+        if let Some(affix) = affixes.iter_mut().find(|a| a.name == affix_name) {
+            affix.set_description(description.to_string());
+        } else {
+            let mut new_affix = Affix::new(affix_name, Vec::new());
+            new_affix.set_description(description.to_string());
+            affixes.push(new_affix);
+        }
+    }
+
+    /// Sets the power cost of an affix, creating the affix if it does not exist yet.
+    ///
+    /// Overrides the default cost (the sum of the affix's attribute initial values) used by
+    /// [`GeneratorOptions::power_budget`].
+    pub fn set_affix_power_cost(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        is_prefix: bool,
+        affix_name: &str,
+        power_cost: f64,
+    ) {
+        let key_pair = (type_name.to_string(), subtype.to_string());
+        let (prefixes, suffixes) = self
+            .item_affixes
+            .entry(key_pair)
+            .or_insert((Vec::new(), Vec::new()));
+        let affixes = if is_prefix { prefixes } else { suffixes };
+
+        if let Some(affix) = affixes.iter_mut().find(|a| a.name == affix_name) {
+            affix.set_power_cost(power_cost);
+        } else {
+            let mut new_affix = Affix::new(affix_name, Vec::new());
+            new_affix.set_power_cost(power_cost);
+            affixes.push(new_affix);
+        }
+    }
+
+    /// Sets a metadata value on an affix, creating the affix if it does not exist yet.
+    ///
+    /// The metadata is merged into an item's metadata whenever the affix is applied, so an
+    /// affix can flag behavior without carrying any attributes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_affix_metadata("weapon", "", true, "Soulbound", "soulbound", serde_json::json!(true));
+    /// ```
+    pub fn set_affix_metadata(
+        &mut self,
+        type_name: &str,
+        subtype: &str,
+        is_prefix: bool,
+        affix_name: &str,
+        key: &str,
+        value: serde_json::Value,
+    ) {
+        let key_pair = (type_name.to_string(), subtype.to_string());
+        let (prefixes, suffixes) = self
+            .item_affixes
+            .entry(key_pair)
+            .or_insert((Vec::new(), Vec::new()));
+        let affixes = if is_prefix { prefixes } else { suffixes };
+
+        if let Some(affix) = affixes.iter_mut().find(|a| a.name == affix_name) {
+            affix.set_metadata(key, value);
+        } else {
+            let mut new_affix = Affix::new(affix_name, Vec::new());
+            new_affix.set_metadata(key, value);
+            affixes.push(new_affix);
+        }
+    }
+
     /// Convenience method to add a prefix attribute. Equivalent to calling
     /// [`set_affix_attribute`](Self::set_affix_attribute) with `is_prefix = true`.
     pub fn set_prefix_attribute(
@@ -502,6 +1689,52 @@ impl PraedaGenerator {
         self.set_affix_attribute(type_name, subtype, false, affix_name, attribute);
     }
 
+    /// Sets a weighted distribution over how many affixes an item rolls.
+    ///
+    /// Each `(count, weight)` pair gives the relative weight of an item getting `count` affixes.
+    /// Items have a prefix and a suffix slot, so counts above 2 fill both. A single affix goes
+    /// to a random slot that has affixes available. When set, this replaces the per-slot
+    /// `affix_chance` roll for affixes; an empty distribution (the default) restores it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // 50% no affixes, 30% one, 20% both
+    /// generator.set_affix_count_distribution(vec![(0, 50), (1, 30), (2, 20)]);
+    /// ```
+    pub fn set_affix_count_distribution(&mut self, distribution: Vec<(u32, i32)>) {
+        self.affix_count_distribution = distribution;
+    }
+
+    /// Get the affix count distribution (empty when per-slot chance rolls are used)
+    pub fn get_affix_count_distribution(&self) -> &[(u32, i32)] {
+        &self.affix_count_distribution
+    }
+
+    /// Sets which affix slots an item type can roll.
+    ///
+    /// Disallowed slots are never filled for items of the type, regardless of configured
+    /// affixes or affix chance. Types default to allowing both slots.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Consumables get "of Healing" but never a prefix
+    /// generator.set_affix_slots("consumable", false, true);
+    /// ```
+    pub fn set_affix_slots(&mut self, type_name: &str, allow_prefix: bool, allow_suffix: bool) {
+        self.affix_slots
+            .insert(type_name.to_string(), (allow_prefix, allow_suffix));
+    }
+
+    /// Get the (allow_prefix, allow_suffix) slot rules for an item type
+    pub fn get_affix_slots(&self, type_name: &str) -> (bool, bool) {
+        self.affix_slots
+            .get(type_name)
+            .copied()
+            .unwrap_or((true, true))
+    }
+
     /// Get prefixes for a type/subtype
     pub fn get_prefixes(&self, type_name: &str, subtype: &str) -> Vec<Affix> {
         if let Some((prefixes, _)) =
@@ -549,25 +1782,42 @@ impl PraedaGenerator {
     ///
     /// generator.load_data(toml_str)?;
     /// ```
+    ///
+    /// Quality data (including fractional weights from
+    /// [`set_quality_data_f64`](Self::set_quality_data_f64)) and item types are replaced
+    /// wholesale, discarding any set before loading, and each type/subtype in the TOML replaces
+    /// that key's attributes, names and affixes. Anything else the TOML does not cover, such as
+    /// other type/subtypes or settings made through setters, is kept. Use [`load_data_merge`](Self::load_data_merge) to add to the existing
+    /// configuration instead, or [`reload_data`](Self::reload_data) to start from a fresh one.
     pub fn load_data(&mut self, toml_data: &str) -> Result<()> {
         let config: crate::models::TomlConfig = toml::from_str(toml_data)?;
+        self.apply_config(config);
+        Ok(())
+    }
 
-        // Load quality data
+    /// Applies a parsed TOML configuration with [`load_data`](Self::load_data) semantics
+    fn apply_config(&mut self, config: crate::models::TomlConfig) {
+        // Load quality data, dropping fractional weights that could not be mixed with it
         self.quality_data = config.quality_data;
+        self.quality_data_f64.clear();
 
         // Load item types
         self.item_types = config.item_types;
 
         // Load item attributes from TOML structure into HashMap
-        for item_attrs in config.item_attributes {
+        for item_attrs in config.item_attributes.into_entries() {
             let key = (item_attrs.item_type, item_attrs.subtype);
             self.item_attributes.insert(key, item_attrs.attributes);
         }
 
         // Load item list from TOML structure into HashMap
+        let mut inline_affixes = Vec::new();
         for item in config.item_list {
             let key = (item.item_type.clone(), item.subtype.clone());
             self.item_list.insert(key.clone(), item.names.clone());
+            if !item.prefixes.is_empty() || !item.suffixes.is_empty() {
+                inline_affixes.push((key.clone(), item.prefixes, item.suffixes));
+            }
 
             // Load per-item metadata if present
             for (item_name, metadata) in item.item_metadata {
@@ -594,9 +1844,105 @@ impl PraedaGenerator {
             }
         }
 
+        // Fold affixes defined inline in item lists into the same keys
+        for (key, prefixes, suffixes) in inline_affixes {
+            let (existing_prefixes, existing_suffixes) = self.item_affixes.entry(key).or_default();
+            existing_prefixes.extend(prefixes);
+            existing_suffixes.extend(suffixes);
+        }
+    }
+
+    /// Loads generator configuration from a TOML string, adding to the existing configuration.
+    ///
+    /// Unlike [`load_data`](Self::load_data), nothing configured before loading is discarded;
+    /// use [`reload_data`](Self::reload_data) to start from a fresh configuration instead.
+    /// Where the TOML and the existing configuration overlap, the TOML wins:
+    ///
+    /// * Qualities and item types from the TOML are added; an existing one takes the TOML weight.
+    ///   Qualities in the TOML replace any fractional weights, which cannot be mixed with them
+    /// * Attributes are added per type/subtype; one with an existing name replaces it
+    /// * Item names are appended, skipping names already present
+    /// * Affixes are added per type/subtype; one with an existing name replaces it
+    /// * Subtype metadata keys are added, replacing existing keys
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.set_quality_data("cursed", 5);
+    /// generator.load_data_merge(&std::fs::read_to_string("loot_config.toml")?)?;
+    /// assert!(generator.has_quality("cursed"));
+    /// ```
+    pub fn load_data_merge(&mut self, toml_data: &str) -> Result<()> {
+        let config: crate::models::TomlConfig = toml::from_str(toml_data)?;
+
+        // Integer weights cannot be mixed with fractional ones, which they replace
+        if !config.quality_data.is_empty() {
+            self.quality_data_f64.clear();
+        }
+        self.quality_data.extend(config.quality_data);
+
+        for item_type in config.item_types {
+            if let Some(existing) = self
+                .item_types
+                .iter_mut()
+                .find(|it| it.item_type == item_type.item_type)
+            {
+                *existing = item_type;
+            } else {
+                self.item_types.push(item_type);
+            }
+        }
+
+        for item_attrs in config.item_attributes.into_entries() {
+            for attribute in item_attrs.attributes {
+                self.replace_attribute(&item_attrs.item_type, &item_attrs.subtype, attribute);
+            }
+        }
+
+        for affixes in config.item_affixes {
+            let key = (affixes.item_type.clone(), affixes.subtype.clone());
+            if !affixes.metadata.is_empty() {
+                self.subtype_metadata.entry(key.clone()).or_default().extend(affixes.metadata);
+            }
+            self.merge_affixes(key, affixes.prefixes, affixes.suffixes);
+        }
+
+        for item in config.item_list {
+            let key = (item.item_type.clone(), item.subtype.clone());
+            let names = self.item_list.entry(key.clone()).or_default();
+            for name in item.names {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+
+            for (item_name, metadata) in item.item_metadata {
+                for (meta_key, meta_value) in metadata {
+                    self.set_item_name_metadata(&key.0, &key.1, &item_name, &meta_key, meta_value);
+                }
+            }
+
+            // Inline affixes land after the affix tables, as in load_data
+            self.merge_affixes(key, item.prefixes, item.suffixes);
+        }
+
         Ok(())
     }
 
+    /// Adds affixes to a type/subtype, replacing existing affixes with the same name
+    fn merge_affixes(&mut self, key: (String, String), prefixes: Vec<Affix>, suffixes: Vec<Affix>) {
+        let (existing_prefixes, existing_suffixes) = self.item_affixes.entry(key).or_default();
+        for (existing, incoming) in [(existing_prefixes, prefixes), (existing_suffixes, suffixes)] {
+            for affix in incoming {
+                if let Some(pos) = existing.iter().position(|a| a.name == affix.name) {
+                    existing[pos] = affix;
+                } else {
+                    existing.push(affix);
+                }
+            }
+        }
+    }
+
     /// Loads generator configuration from a TOML file.
     ///
     /// This is the recommended way to configure a generator - create a TOML file with your
@@ -619,10 +1965,137 @@ impl PraedaGenerator {
         self.load_data(&toml_str)
     }
 
+    /// Replaces the generator configuration with a TOML string, keeping stored loot.
+    ///
+    /// Unlike [`load_data`](Self::load_data), which only replaces the sections present in the
+    /// TOML, this first clears the tables a TOML file describes: qualities, item types,
+    /// attributes, item names, affixes and metadata. Settings made through setters, such as
+    /// loot storage, the item cap or the name fallback, are kept, as is loot stored under keys.
+    /// If the TOML fails to parse, the current configuration is kept unchanged.
+    pub fn reload_data(&mut self, toml_data: &str) -> Result<()> {
+        let config: crate::models::TomlConfig = toml::from_str(toml_data)?;
+        self.clear_config();
+        self.apply_config(config);
+        Ok(())
+    }
+
+    /// Clears the configuration tables a TOML file describes, leaving setter-controlled settings
+    fn clear_config(&mut self) {
+        self.quality_data.clear();
+        self.quality_data_f64.clear();
+        self.item_types.clear();
+        self.item_attributes.clear();
+        self.item_list.clear();
+        self.item_affixes.clear();
+        self.subtype_metadata.clear();
+        self.item_name_metadata.clear();
+    }
+
+    /// Replaces the generator configuration with a TOML file, keeping stored loot.
+    ///
+    /// Intended for hot-reloading a running game server's loot tables. See
+    /// [`reload_data`](Self::reload_data).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// generator.generate_loot(&options, &Default::default(), "issued")?;
+    /// generator.reload_data_from_file("loot_config.toml")?;
+    /// assert!(!generator.get_loot("issued").is_empty());
+    /// ```
+    pub fn reload_data_from_file(&mut self, path: &str) -> Result<()> {
+        let toml_str = fs::read_to_string(path)?;
+        self.reload_data(&toml_str)
+    }
+
+    /// Returns a serializable copy of the current configuration, e.g. for a debug overlay.
+    ///
+    /// This is the inverse of [`load_data`](Self::load_data): qualities, item types with their
+    /// subtypes and weights, attributes, item names, affixes and metadata are gathered into a
+    /// [`ConfigSnapshot`]. Stored loot is not included and the generator is left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let snapshot = generator.get_config_snapshot();
+    /// println!("{}", serde_json::to_string_pretty(&snapshot)?);
+    /// ```
+    pub fn get_config_snapshot(&self) -> ConfigSnapshot {
+        fn sorted_keys<'a>(keys: impl Iterator<Item = &'a (String, String)>) -> Vec<(String, String)> {
+            let mut keys: Vec<(String, String)> = keys.cloned().collect();
+            keys.sort();
+            keys.dedup();
+            keys
+        }
+
+        let item_attributes = sorted_keys(self.item_attributes.keys())
+            .into_iter()
+            .map(|(item_type, subtype)| TomlItemAttributes {
+                attributes: self.item_attributes[&(item_type.clone(), subtype.clone())].clone(),
+                item_type,
+                subtype,
+            })
+            .collect();
+
+        let name_keys: Vec<(String, String)> = self
+            .item_name_metadata
+            .keys()
+            .map(|(item_type, subtype, _)| (item_type.clone(), subtype.clone()))
+            .collect();
+        let item_list = sorted_keys(self.item_list.keys().chain(name_keys.iter()))
+            .into_iter()
+            .map(|(item_type, subtype)| {
+                let key = (item_type.clone(), subtype.clone());
+                let item_metadata = self
+                    .item_name_metadata
+                    .iter()
+                    .filter(|((t, s, _), _)| *t == item_type && *s == subtype)
+                    .map(|((_, _, name), metadata)| (name.clone(), metadata.clone()))
+                    .collect();
+                TomlItemList {
+                    names: self.item_list.get(&key).cloned().unwrap_or_default(),
+                    item_metadata,
+                    prefixes: Vec::new(),
+                    suffixes: Vec::new(),
+                    item_type,
+                    subtype,
+                }
+            })
+            .collect();
+
+        let affix_keys = self.item_affixes.keys().chain(self.subtype_metadata.keys());
+        let item_affixes = sorted_keys(affix_keys)
+            .into_iter()
+            .map(|(item_type, subtype)| {
+                let key = (item_type.clone(), subtype.clone());
+                let (prefixes, suffixes) = self.item_affixes.get(&key).cloned().unwrap_or_default();
+                TomlItemAffixes {
+                    prefixes,
+                    suffixes,
+                    metadata: self.subtype_metadata.get(&key).cloned().unwrap_or_default(),
+                    item_type,
+                    subtype,
+                }
+            })
+            .collect();
+
+        ConfigSnapshot {
+            quality_data: self.quality_data.clone(),
+            quality_data_f64: self.quality_data_f64.clone(),
+            quality_metadata: self.quality_metadata.clone(),
+            item_types: self.item_types.clone(),
+            item_attributes,
+            item_list,
+            item_affixes,
+        }
+    }
+
     /// Generates a collection of items based on the provided options.
     ///
-    /// Creates `options.number_of_items` items with random qualities, types, affixes, and attributes.
+    /// Creates up to `options.number_of_items` items with random qualities, types, affixes, and attributes.
     /// The generated items are also stored internally and can be retrieved using [`get_loot`](Self::get_loot).
+    /// Fewer items are returned when empty drops are configured with
+    /// [`set_empty_drop_weight`](Self::set_empty_drop_weight).
     ///
     /// # Arguments
     ///
@@ -640,6 +2113,9 @@ impl PraedaGenerator {
     /// - No item types are configured
     /// - No qualities are configured
     /// - Item type/subtype configuration is incomplete
+    /// - The options fail [`GeneratorOptions::validate`]
+    /// - `options.number_of_items` exceeds [`get_max_items`](Self::get_max_items)
+    /// - An override names a quality, type or subtype that is not configured
     ///
     /// # Example
     ///
@@ -651,6 +2127,7 @@ impl PraedaGenerator {
     ///     affix_chance: 0.25,
     ///     linear: true,
     ///     scaling_factor: 1.5,
+    ///     ..Default::default()
     /// };
     ///
     /// let items = generator.generate_loot(&options, &Default::default(), "bosses_loot")?;
@@ -661,24 +2138,500 @@ impl PraedaGenerator {
         overrides: &GeneratorOverrides,
         key: &str,
     ) -> Result<Vec<Item>> {
+        self.generate_loot_with_hook(options, overrides, key, &mut |_| {})
+    }
+
+    /// Generates loot like [`generate_loot`](Self::generate_loot), overriding only the scaling
+    /// fields of `base`.
+    ///
+    /// `linear` and `scaling_factor` replace the ones in `base`, and `base.curve` is ignored so
+    /// `linear` takes effect. Everything else, including the seed, comes from `base`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let linear = generator.generate_loot_scaled(&options, true, 1.5, "linear")?;
+    /// let exponential = generator.generate_loot_scaled(&options, false, 1.1, "exponential")?;
+    /// ```
+    pub fn generate_loot_scaled(
+        &mut self,
+        base: &GeneratorOptions,
+        linear: bool,
+        scaling_factor: f64,
+        key: &str,
+    ) -> Result<Vec<Item>> {
+        let options = GeneratorOptions {
+            linear,
+            scaling_factor,
+            curve: None,
+            ..base.clone()
+        };
+        self.generate_loot(&options, &GeneratorOverrides::empty(), key)
+    }
+
+    /// Generates items like [`generate_loot`](Self::generate_loot), running `hook` on each item.
+    ///
+    /// The hook is called once per item after its attributes and metadata are in place and
+    /// before the batch is stored, so any changes it makes are also returned by
+    /// [`get_loot`](Self::get_loot).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let items = generator.generate_loot_with_hook(&options, &overrides, "chest", &mut |item| {
+    ///     item.set_metadata("durability", serde_json::json!(100));
+    /// })?;
+    /// ```
+    pub fn generate_loot_with_hook(
+        &mut self,
+        options: &GeneratorOptions,
+        overrides: &GeneratorOverrides,
+        key: &str,
+        hook: &mut dyn FnMut(&mut Item),
+    ) -> Result<Vec<Item>> {
+        let seed = Self::run_seed(options);
+        let mut rng = Self::loot_rng(seed);
+        let mut name_deck = options.unique_names_within_batch.then(NameDeck::new);
+        let items = self.generate_batch(options, overrides, &mut rng, hook, name_deck.as_mut())?;
+        self.last_seed = Some(seed);
+        self.store_items(key, &items);
+        Ok(items)
+    }
+
+    /// Generates loot like [`generate_loot`](Self::generate_loot), collecting per-item errors
+    /// instead of aborting the batch.
+    ///
+    /// Each item that fails to generate is skipped and its error is returned alongside the
+    /// items that succeeded, so one misconfigured subtype does not discard the rest of a large
+    /// table. If the options themselves are invalid, no items are generated and that error is
+    /// the only one returned. The successful items are stored under `key` as usual.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let (items, errors) = generator.generate_loot_lenient(&options, &overrides, "chest");
+    /// for error in &errors {
+    ///     eprintln!("skipped item: {}", error);
+    /// }
+    /// ```
+    pub fn generate_loot_lenient(
+        &mut self,
+        options: &GeneratorOptions,
+        overrides: &GeneratorOverrides,
+        key: &str,
+    ) -> (Vec<Item>, Vec<PraedaError>) {
         let mut items = Vec::new();
+        let mut errors = Vec::new();
+        if let Err(e) = self.validate_batch(options) {
+            errors.push(e);
+            return (items, errors);
+        }
+
+        let seed = Self::run_seed(options);
+        self.last_seed = Some(seed);
+        let mut rng = Self::loot_rng(seed);
+        let mut name_deck = options.unique_names_within_batch.then(NameDeck::new);
         for _ in 0..options.number_of_items {
-            let item = self.generate_item(options, overrides)?;
+            if self.roll_empty_drop(&mut rng) {
+                continue;
+            }
+            match self.generate_item(options, overrides, &mut rng, name_deck.as_mut()) {
+                Ok(item) => items.push(item),
+                Err(e) => errors.push(e),
+            }
+        }
+        if let Some(sort) = &options.sort {
+            self.sort_items(&mut items, sort, &options.level_attribute_name);
+        }
+
+        self.store_items(key, &items);
+        (items, errors)
+    }
+
+    /// Generates items like [`generate_loot`](Self::generate_loot) without storing them.
+    ///
+    /// Only `&self` is needed, so a configured generator can be wrapped in an `Arc` and
+    /// shared between threads: `PraedaGenerator` is `Sync`, and each call uses its own RNG.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let generator = std::sync::Arc::new(generator);
+    /// let shared = std::sync::Arc::clone(&generator);
+    /// let handle = std::thread::spawn(move || {
+    ///     shared.generate_loot_shared(&options, &GeneratorOverrides::empty())
+    /// });
+    /// ```
+    pub fn generate_loot_shared(
+        &self,
+        options: &GeneratorOptions,
+        overrides: &GeneratorOverrides,
+    ) -> Result<Vec<Item>> {
+        let mut rng = Self::loot_rng(Self::run_seed(options));
+        let mut name_deck = options.unique_names_within_batch.then(NameDeck::new);
+        self.generate_batch(options, overrides, &mut rng, &mut |_| {}, name_deck.as_mut())
+    }
+
+    /// Generates loot like [`generate_loot`](Self::generate_loot) and also returns a
+    /// [`GenerationReport`] with counters for profiling the run.
+    ///
+    /// The counters are only collected here; the regular generation methods do no extra work.
+    /// Items are stored under `key` as usual.
+    pub fn generate_loot_reported(
+        &mut self,
+        options: &GeneratorOptions,
+        overrides: &GeneratorOverrides,
+        key: &str,
+    ) -> Result<(Vec<Item>, GenerationReport)> {
+        let seed = Self::run_seed(options);
+        let mut rng = CountingRng {
+            inner: Self::loot_rng(seed),
+            calls: 0,
+        };
+        let mut name_deck = options.unique_names_within_batch.then(NameDeck::new);
+        let items = self.generate_batch(options, overrides, &mut rng, &mut |_| {}, name_deck.as_mut())?;
+
+        let affixes_applied = items
+            .iter()
+            .map(|item| {
+                [item.get_prefix(), item.get_suffix()]
+                    .iter()
+                    .filter(|affix| !affix.get_name().is_empty())
+                    .count() as u64
+            })
+            .sum();
+        let report = GenerationReport {
+            items_generated: items.len(),
+            rng_calls: rng.calls,
+            affixes_applied,
+            unique_name_retries: name_deck.map_or(0, |deck| deck.refills),
+            seed,
+        };
+
+        self.last_seed = Some(seed);
+        self.store_items(key, &items);
+        Ok((items, report))
+    }
+
+    /// Simulates `trials` drops and returns aggregated statistics, e.g. "how many legendaries
+    /// does a player see in 1000 chests?".
+    ///
+    /// Items are tallied as they are generated and not kept, so nothing is stored in the loot
+    /// list. Results are reproducible when `options.seed` is set; `options.number_of_items` is
+    /// ignored in favor of `trials`.
+    pub fn simulate(
+        &self,
+        options: &GeneratorOptions,
+        overrides: &GeneratorOverrides,
+        trials: usize,
+    ) -> Result<LootStats> {
+        options.validate()?;
+
+        let mut rng = Self::loot_rng(Self::run_seed(options));
+        let mut name_deck = options.unique_names_within_batch.then(NameDeck::new);
+        let mut stats = LootStats {
+            trials,
+            ..Default::default()
+        };
+        for _ in 0..trials {
+            if self.roll_empty_drop(&mut rng) {
+                continue;
+            }
+            let item = self.generate_item(options, overrides, &mut rng, name_deck.as_mut())?;
+            stats.record(&item);
+        }
+
+        Ok(stats)
+    }
+
+    /// Rolls whether the next drop is empty, weighing the empty drop weight against the total
+    /// quality weight
+    fn roll_empty_drop<R: Rng>(&self, rng: &mut R) -> bool {
+        if self.empty_drop_weight <= 0 {
+            return false;
+        }
+
+        let empty_weight = self.empty_drop_weight as f64;
+        let quality_weight: f64 = self
+            .quality_data
+            .values()
+            .map(|&weight| weight.max(0) as f64)
+            .chain(self.quality_data_f64.values().map(|&weight| weight.max(0.0)))
+            .sum();
+        rng.random::<f64>() * (empty_weight + quality_weight) < empty_weight
+    }
+
+    /// Seed for a generation run: the one in the options, or a fresh one from entropy
+    fn run_seed(options: &GeneratorOptions) -> u64 {
+        options.seed.unwrap_or_else(|| rand::rng().random())
+    }
+
+    /// Creates the RNG for a generation run from its seed
+    fn loot_rng(seed: u64) -> LootRng {
+        LootRng::seed_from_u64(seed)
+    }
+
+    /// Checks the options and item count of a batch before anything is generated
+    fn validate_batch(&self, options: &GeneratorOptions) -> Result<()> {
+        options.validate()?;
+        if options.number_of_items > self.max_items {
+            return Err(PraedaError::InvalidData(format!(
+                "number_of_items ({}) exceeds the maximum of {}",
+                options.number_of_items, self.max_items
+            )));
+        }
+        Ok(())
+    }
+
+    /// Generates up to `options.number_of_items` items, running `hook` on each
+    fn generate_batch<R: Rng>(
+        &self,
+        options: &GeneratorOptions,
+        overrides: &GeneratorOverrides,
+        rng: &mut R,
+        hook: &mut dyn FnMut(&mut Item),
+        mut name_deck: Option<&mut NameDeck>,
+    ) -> Result<Vec<Item>> {
+        self.validate_batch(options)?;
+
+        let mut items = Vec::new();
+        for _ in 0..options.number_of_items {
+            if self.roll_empty_drop(rng) {
+                continue;
+            }
+            let mut item = self.generate_item(options, overrides, rng, name_deck.as_deref_mut())?;
+            hook(&mut item);
             items.push(item);
         }
-        self.loot_list.insert(key.to_string(), items.clone());
-        Ok(items)
+        if let Some(sort) = &options.sort {
+            self.sort_items(&mut items, sort, &options.level_attribute_name);
+        }
+        Ok(items)
+    }
+
+    /// Sorts a batch best first by `sort`, breaking ties by name
+    fn sort_items(&self, items: &mut [Item], sort: &SortKey, level_attribute_name: &str) {
+        let value = |item: &Item, name: &str| item.get_attribute(name).map(|a| a.get_initial_value());
+        items.sort_by(|a, b| {
+            let order = match sort {
+                SortKey::QualityRank => {
+                    let rank = |item: &Item| self.get_quality_rank(item.get_quality());
+                    // Unranked qualities compare as None, which sorts below any rank
+                    rank(b).cmp(&rank(a))
+                }
+                SortKey::Level => value(b, level_attribute_name)
+                    .partial_cmp(&value(a, level_attribute_name))
+                    .unwrap_or(std::cmp::Ordering::Equal),
+                SortKey::Attribute(name) => value(b, name)
+                    .partial_cmp(&value(a, name))
+                    .unwrap_or(std::cmp::Ordering::Equal),
+            };
+            order.then_with(|| a.get_name().cmp(b.get_name()))
+        });
+    }
+
+    /// Generate loot and return as JSON string
+    pub fn generate_loot_json(
+        &mut self,
+        options: &GeneratorOptions,
+        overrides: &GeneratorOverrides,
+        key: &str,
+    ) -> Result<String> {
+        let items = self.generate_loot(options, overrides, key)?;
+        Ok(serde_json::to_string(&items)?)
+    }
+
+    /// Generate loot and return as an indented JSON string, for debugging and file output
+    pub fn generate_loot_json_pretty(
+        &mut self,
+        options: &GeneratorOptions,
+        overrides: &GeneratorOverrides,
+        key: &str,
+    ) -> Result<String> {
+        let items = self.generate_loot(options, overrides, key)?;
+        Ok(serde_json::to_string_pretty(&items)?)
+    }
+
+    /// Generate loot and return it as MessagePack bytes
+    ///
+    /// Items are encoded with named fields, matching the JSON layout. Decode with
+    /// [`loot_from_msgpack`](Self::loot_from_msgpack).
+    #[cfg(feature = "msgpack")]
+    pub fn generate_loot_msgpack(
+        &mut self,
+        options: &GeneratorOptions,
+        overrides: &GeneratorOverrides,
+        key: &str,
+    ) -> Result<Vec<u8>> {
+        let items = self.generate_loot(options, overrides, key)?;
+        Ok(rmp_serde::to_vec_named(&items)?)
+    }
+
+    /// Decode items produced by [`generate_loot_msgpack`](Self::generate_loot_msgpack)
+    #[cfg(feature = "msgpack")]
+    pub fn loot_from_msgpack(bytes: &[u8]) -> Result<Vec<Item>> {
+        Ok(rmp_serde::from_slice(bytes)?)
+    }
+
+    /// Serializes the generator configuration to bincode.
+    ///
+    /// Useful for shipping loot tables inside a game binary without parsing TOML at startup.
+    /// Stored loot and a custom [`NameFallback`] are not included.
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> Result<Vec<u8>> {
+        fn encode_metadata<K: Clone + Eq + std::hash::Hash>(
+            metadata: &HashMap<K, HashMap<String, serde_json::Value>>,
+        ) -> Result<HashMap<K, HashMap<String, String>>> {
+            metadata
+                .iter()
+                .map(|(key, values)| {
+                    let encoded = values
+                        .iter()
+                        .map(|(name, value)| Ok((name.clone(), serde_json::to_string(value)?)))
+                        .collect::<Result<_>>()?;
+                    Ok((key.clone(), encoded))
+                })
+                .collect()
+        }
+
+        let mut item_affixes = self.item_affixes.clone();
+        let mut affix_metadata = HashMap::new();
+        for ((item_type, subtype), (prefixes, suffixes)) in item_affixes.iter_mut() {
+            for (is_prefix, affixes) in [(true, prefixes), (false, suffixes)] {
+                for affix in affixes.iter_mut() {
+                    let metadata = std::mem::take(&mut affix.metadata);
+                    if !metadata.is_empty() {
+                        affix_metadata.insert(
+                            (item_type.clone(), subtype.clone(), is_prefix, affix.name.clone()),
+                            metadata,
+                        );
+                    }
+                }
+            }
+        }
+
+        let snapshot = GeneratorSnapshot {
+            quality_data: self.quality_data.clone(),
+            quality_data_f64: self.quality_data_f64.clone(),
+            item_types: self.item_types.clone(),
+            item_list: self.item_list.clone(),
+            quality_item_list: self.quality_item_list.clone(),
+            localized_item_list: self.localized_item_list.clone(),
+            item_attributes: self.item_attributes.clone(),
+            attribute_overrides: self.attribute_overrides.clone(),
+            quality_attributes: self.quality_attributes.clone(),
+            item_affixes,
+            subtype_metadata: encode_metadata(&self.subtype_metadata)?,
+            item_name_metadata: encode_metadata(&self.item_name_metadata)?,
+            quality_metadata: encode_metadata(&self.quality_metadata)?,
+            affix_metadata: encode_metadata(&affix_metadata)?,
+            quality_min_affixes: self.quality_min_affixes.clone(),
+            quality_ranks: self.quality_ranks.clone(),
+            quality_affix_tiers: self.quality_affix_tiers.clone(),
+            subtype_level_offsets: self.subtype_level_offsets.clone(),
+            quality_level_bonuses: self.quality_level_bonuses.clone(),
+            affix_slots: self.affix_slots.clone(),
+            empty_drop_weight: self.empty_drop_weight,
+            affix_count_distribution: self.affix_count_distribution.clone(),
+            attribute_templates: self.attribute_templates.clone(),
+            attribute_groups: self.attribute_groups.clone(),
+            max_items: self.max_items,
+            store_loot: self.store_loot,
+            require_names: self.require_names,
+        };
+        Ok(bincode::serialize(&snapshot)?)
     }
 
-    /// Generate loot and return as JSON string
-    pub fn generate_loot_json(
-        &mut self,
-        options: &GeneratorOptions,
-        overrides: &GeneratorOverrides,
-        key: &str,
-    ) -> Result<String> {
-        let items = self.generate_loot(options, overrides, key)?;
-        Ok(serde_json::to_string(&items)?)
+    /// Creates a generator from configuration produced by [`to_bincode`](Self::to_bincode)
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(bytes: &[u8]) -> Result<PraedaGenerator> {
+        fn decode_metadata<K: Eq + std::hash::Hash>(
+            metadata: HashMap<K, HashMap<String, String>>,
+        ) -> Result<HashMap<K, HashMap<String, serde_json::Value>>> {
+            metadata
+                .into_iter()
+                .map(|(key, values)| {
+                    let decoded = values
+                        .into_iter()
+                        .map(|(name, value)| Ok((name, serde_json::from_str(&value)?)))
+                        .collect::<Result<_>>()?;
+                    Ok((key, decoded))
+                })
+                .collect()
+        }
+
+        let snapshot: GeneratorSnapshot = bincode::deserialize(bytes)?;
+        let mut item_affixes = snapshot.item_affixes;
+        for ((item_type, subtype, is_prefix, affix_name), metadata) in
+            decode_metadata(snapshot.affix_metadata)?
+        {
+            if let Some((prefixes, suffixes)) = item_affixes.get_mut(&(item_type, subtype)) {
+                let affixes = if is_prefix { prefixes } else { suffixes };
+                if let Some(affix) = affixes.iter_mut().find(|a| a.name == affix_name) {
+                    affix.metadata = metadata;
+                }
+            }
+        }
+
+        Ok(PraedaGenerator {
+            quality_data: snapshot.quality_data,
+            quality_data_f64: snapshot.quality_data_f64,
+            item_types: snapshot.item_types,
+            item_list: snapshot.item_list,
+            quality_item_list: snapshot.quality_item_list,
+            localized_item_list: snapshot.localized_item_list,
+            item_attributes: snapshot.item_attributes,
+            attribute_overrides: snapshot.attribute_overrides,
+            quality_attributes: snapshot.quality_attributes,
+            item_affixes,
+            subtype_metadata: decode_metadata(snapshot.subtype_metadata)?,
+            item_name_metadata: decode_metadata(snapshot.item_name_metadata)?,
+            quality_metadata: decode_metadata(snapshot.quality_metadata)?,
+            quality_min_affixes: snapshot.quality_min_affixes,
+            quality_ranks: snapshot.quality_ranks,
+            quality_affix_tiers: snapshot.quality_affix_tiers,
+            subtype_level_offsets: snapshot.subtype_level_offsets,
+            quality_level_bonuses: snapshot.quality_level_bonuses,
+            affix_slots: snapshot.affix_slots,
+            empty_drop_weight: snapshot.empty_drop_weight,
+            affix_count_distribution: snapshot.affix_count_distribution,
+            attribute_templates: snapshot.attribute_templates,
+            attribute_groups: snapshot.attribute_groups,
+            max_items: snapshot.max_items,
+            store_loot: snapshot.store_loot,
+            require_names: snapshot.require_names,
+            ..PraedaGenerator::new()
+        })
+    }
+
+    /// Counts items per quality in a generated batch
+    pub fn summarize_qualities(items: &[Item]) -> HashMap<String, usize> {
+        Self::summarize_by(items, |item| item.get_quality())
+    }
+
+    /// Counts items per item type in a generated batch
+    pub fn summarize_types(items: &[Item]) -> HashMap<String, usize> {
+        Self::summarize_by(items, |item| item.get_type())
+    }
+
+    /// Counts items per subtype in a generated batch
+    pub fn summarize_subtypes(items: &[Item]) -> HashMap<String, usize> {
+        Self::summarize_by(items, |item| item.get_subtype())
+    }
+
+    fn summarize_by(items: &[Item], field: impl Fn(&Item) -> &str) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for item in items {
+            *counts.entry(field(item).to_string()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Keeps a generated batch under `key`, unless storage is disabled
+    fn store_items(&mut self, key: &str, items: &[Item]) {
+        if self.store_loot {
+            self.loot_list.insert(key.to_string(), items.to_vec());
+        }
     }
 
     /// Get previously generated loot by key
@@ -689,99 +2642,237 @@ impl PraedaGenerator {
             .unwrap_or_default()
     }
 
+    /// Borrow previously generated loot by key without cloning it
+    ///
+    /// Returns `None` if nothing is stored under `key`. Use [`get_loot`](Self::get_loot) for an
+    /// owned copy.
+    pub fn peek_loot(&self, key: &str) -> Option<&[Item]> {
+        self.loot_list.get(key).map(|items| items.as_slice())
+    }
+
+    /// Removes and returns the last stored item for a key.
+    ///
+    /// Models a loot pile that is drawn from one item at a time. Once the last item is taken
+    /// the key is removed. Returns `None` when no items remain.
+    pub fn take_loot_item(&mut self, key: &str) -> Option<Item> {
+        let items = self.loot_list.get_mut(key)?;
+        let item = items.pop();
+        if items.is_empty() {
+            self.loot_list.remove(key);
+        }
+        item
+    }
+
+    /// Seed used by the most recent call that stores loot, e.g.
+    /// [`generate_loot`](Self::generate_loot).
+    ///
+    /// When `options.seed` is `None` a seed is drawn from entropy, so passing this value back
+    /// as `options.seed` with the same configuration reproduces the run. Returns `None` before
+    /// the first such call. [`generate_loot_shared`](Self::generate_loot_shared) and
+    /// [`simulate`](Self::simulate) only borrow the generator and do not update it.
+    pub fn last_seed(&self) -> Option<u64> {
+        self.last_seed
+    }
+
+    /// Number of stored items remaining for a key
+    pub fn loot_remaining(&self, key: &str) -> usize {
+        self.loot_list.get(key).map(|items| items.len()).unwrap_or(0)
+    }
+
     /// Get previously generated loot as JSON by key
     pub fn get_loot_json(&self, key: &str) -> Result<String> {
         let loot = self.get_loot(key);
         Ok(serde_json::to_string(&loot)?)
     }
 
-    fn generate_item(
+    /// Get all stored loot as a single JSON object mapping each key to its items
+    ///
+    /// Keys are written in sorted order so the output is stable between runs.
+    pub fn all_loot_json(&self) -> Result<String> {
+        let loot: std::collections::BTreeMap<&String, &Vec<Item>> = self.loot_list.iter().collect();
+        Ok(serde_json::to_string(&loot)?)
+    }
+
+    fn generate_item<R: Rng>(
         &self,
         options: &GeneratorOptions,
         overrides: &GeneratorOverrides,
+        rng: &mut R,
+        name_deck: Option<&mut NameDeck>,
     ) -> Result<Item> {
-        let mut rng = rand::rng();
 
         // Select quality
         let item_quality = if !overrides.quality_override.is_empty() {
+            if !self.has_quality(&overrides.quality_override) {
+                return Err(PraedaError::MissingQuality(overrides.quality_override.clone()));
+            }
             overrides.quality_override.clone()
+        } else if !self.quality_data_f64.is_empty() {
+            if !self.quality_data.is_empty() {
+                return Err(PraedaError::InvalidData(
+                    "Integer and float quality weights cannot be mixed".to_string(),
+                ));
+            }
+            let quality_weights = self.luck_adjusted_quality_weights_f64(options.luck);
+            let quality_weights = whitelist_qualities(quality_weights, &overrides.quality_whitelist)?;
+            self.weighted_random_select_f64(&quality_weights, rng)?
+        } else if options.luck != 0.0 {
+            // Luck boosts are fractional, so boosted integer weights are selected as floats
+            let quality_weights = Cow::Owned(self.luck_adjusted_quality_weights(options.luck));
+            let quality_weights = whitelist_qualities(quality_weights, &overrides.quality_whitelist)?;
+            self.weighted_random_select_f64(&quality_weights, rng)?
         } else {
-            self.weighted_random_select(&self.quality_data, &mut rng)?
+            let quality_weights = whitelist_qualities(
+                Cow::Borrowed(&self.quality_data),
+                &overrides.quality_whitelist,
+            )?;
+            self.weighted_random_select(&quality_weights, rng)?
         };
 
         // Select item type
         let item_type = if !overrides.type_override.is_empty() {
+            if !self.has_item_type(&overrides.type_override) {
+                return Err(PraedaError::MissingItemType(overrides.type_override.clone()));
+            }
             overrides.type_override.clone()
         } else {
             // LCOV_EXCL_START - Rare path: no type override, using weighted selection
+            // Types created only through set_item_subtype have weight 0 and never drop
             let weights: HashMap<String, i32> = self
                 .item_types
                 .iter()
+                .filter(|it| it.weight > 0)
                 .map(|it| (it.item_type.clone(), it.weight))
                 .collect();
-            self.weighted_random_select(&weights, &mut rng)?
+            if weights.is_empty() {
+                return Err(PraedaError::InvalidData(
+                    "No item types with a positive weight to select from".to_string(),
+                ));
+            }
+            self.weighted_random_select(&weights, rng)?
             // LCOV_EXCL_END
         };
 
         // Select subtype
         let subtype = if !overrides.subtype_override.is_empty() {
+            if !self.has_item_subtype(&item_type, &overrides.subtype_override) {
+                return Err(PraedaError::MissingItemSubtype(
+                    item_type,
+                    overrides.subtype_override.clone(),
+                ));
+            }
             overrides.subtype_override.clone()
         } else {
             // LCOV_EXCL_START - Rare path: no subtype override, using weighted selection
-            if let Some(item_type_obj) = self.get_item_type(&item_type) {
-                self.weighted_random_select(item_type_obj.get_subtypes(), &mut rng)?
-            } else {
-                String::new()
+            match self.get_item_type(&item_type) {
+                Some(item_type_obj) => self.weighted_random_select(item_type_obj.get_subtypes(), rng)?,
+                None => return Err(PraedaError::MissingItemType(item_type)),
             }
             // LCOV_EXCL_END
         };
 
         // Select item name
-        let item_name = if let Some(names) = self.item_list.get(&(item_type.clone(), subtype.clone())) {
-            if names.is_empty() {
-                subtype.clone()
-            } else {
-                names[rng.random_range(0..names.len())].clone()
-            }
-        } else {
-            subtype.clone()
-        };
+        let item_name = self.select_item_name(
+            &item_type,
+            &subtype,
+            &item_quality,
+            options.locale.as_deref(),
+            rng,
+            name_deck,
+        )?;
+
+        // Generate item level, offset for the subtype and quality and capped at the configured
+        // maximum
+        let level_range = options.level_variance;
+        let mut generated_level = rng.random_range(
+            (options.base_level - level_range) as i32..=(options.base_level + level_range) as i32,
+        ) as f64;
+        generated_level += self.get_subtype_level_offset(&item_type, &subtype);
+        generated_level += self.get_quality_level_bonus(&item_quality);
+        if let Some(max_level) = options.max_level {
+            generated_level = generated_level.min(max_level);
+        }
+
+        let (valid_prefixes, valid_suffixes) = self.affix_pools(
+            &item_type,
+            &subtype,
+            options.strict_affix_matching,
+            Some(generated_level),
+            Some(&item_quality),
+        );
 
         // Determine if item will have prefix/suffix
-        let will_have_prefix = rng.random::<f64>() < options.affix_chance;
-        let will_have_suffix = rng.random::<f64>() < options.affix_chance;
+        let (will_have_prefix, will_have_suffix) = if self.affix_count_distribution.is_empty() {
+            let affix_chance = options.affix_chance_at_level(generated_level);
+            (rng.random::<f64>() < affix_chance, rng.random::<f64>() < affix_chance)
+        } else {
+            match self.roll_affix_count(rng)? {
+                0 => (false, false),
+                1 => {
+                    let use_prefix = if valid_prefixes.is_empty() || valid_suffixes.is_empty() {
+                        !valid_prefixes.is_empty()
+                    } else {
+                        rng.random_bool(0.5)
+                    };
+                    (use_prefix, !use_prefix)
+                }
+                _ => (true, true),
+            }
+        };
 
         let mut prefix = Affix::empty();
         let mut suffix = Affix::empty();
 
-        if will_have_prefix || will_have_suffix {
-            let valid_keys = vec![
-                ("".to_string(), "".to_string()),
-                (item_type.clone(), "".to_string()),
-                ("".to_string(), subtype.clone()),
-                (item_type.clone(), subtype.clone()),
-            ];
+        let affix_budget = options.affix_budget.unwrap_or(2).min(2);
+        let min_affixes = self.get_quality_min_affixes(&item_quality).min(affix_budget);
 
-            let mut valid_prefixes = Vec::new();
-            let mut valid_suffixes = Vec::new();
+        if will_have_prefix || will_have_suffix || min_affixes > 0 {
+            let level_weighted = options.level_weighted_affixes;
+            let power_budget = options.power_budget.unwrap_or(f64::INFINITY);
+            let remaining_power = |prefix: &Affix, suffix: &Affix| {
+                power_budget - prefix.get_power_cost() - suffix.get_power_cost()
+            };
 
-            for key in valid_keys {
-                if let Some((prefixes, suffixes)) = self.item_affixes.get(&key) {
-                    if will_have_prefix {
-                        valid_prefixes.extend(prefixes.clone());
-                    }
-                    if will_have_suffix {
-                        valid_suffixes.extend(suffixes.clone());
-                    }
-                }
+            let prefixes = Self::affordable_affixes(&valid_prefixes, remaining_power(&prefix, &suffix));
+            if will_have_prefix && !prefixes.is_empty() {
+                prefix = Self::pick_affix(&prefixes, generated_level, level_weighted, rng);
+            }
+
+            let suffixes = Self::affordable_affixes(&valid_suffixes, remaining_power(&prefix, &suffix));
+            if will_have_suffix && !suffixes.is_empty() {
+                suffix = Self::pick_affix(&suffixes, generated_level, level_weighted, rng);
             }
 
-            if will_have_prefix && !valid_prefixes.is_empty() {
-                prefix = valid_prefixes[rng.random_range(0..valid_prefixes.len())].clone();
+            // Keep within the affix budget, dropping a random slot when both rolled
+            let rolled = [&prefix, &suffix].iter().filter(|a| !a.get_name().is_empty()).count() as u32;
+            if rolled > affix_budget {
+                if affix_budget == 0 {
+                    prefix = Affix::empty();
+                    suffix = Affix::empty();
+                } else if rng.random_bool(0.5) {
+                    suffix = Affix::empty();
+                } else {
+                    prefix = Affix::empty();
+                }
             }
 
-            if will_have_suffix && !valid_suffixes.is_empty() {
-                suffix = valid_suffixes[rng.random_range(0..valid_suffixes.len())].clone();
+            // Fill empty slots until the quality's minimum is met, prefix first
+            let mut applied = [&prefix, &suffix].iter().filter(|a| !a.get_name().is_empty()).count() as u32;
+            let prefixes = Self::affordable_affixes(&valid_prefixes, remaining_power(&prefix, &suffix));
+            if applied < min_affixes && prefix.get_name().is_empty() && !prefixes.is_empty() {
+                prefix = Self::pick_affix(&prefixes, generated_level, level_weighted, rng);
+                applied += 1;
+            }
+            let suffixes = Self::affordable_affixes(&valid_suffixes, remaining_power(&prefix, &suffix));
+            if applied < min_affixes && suffix.get_name().is_empty() && !suffixes.is_empty() {
+                suffix = Self::pick_affix(&suffixes, generated_level, level_weighted, rng);
+                applied += 1;
+            }
+            if applied < min_affixes {
+                return Err(PraedaError::InvalidData(format!(
+                    "Quality '{}' requires {} affix(es) but not enough affixes are configured for type={}, subtype={}",
+                    item_quality, min_affixes, item_type, subtype
+                )));
             }
         }
 
@@ -795,7 +2886,14 @@ impl PraedaGenerator {
             HashMap::new(),
         );
 
-        self.calculate_attributes(&mut item, options, &mut rng)?;
+        self.calculate_attributes(&mut item, options, generated_level, rng)?;
+
+        // Attach quality metadata to the item
+        if let Some(metadata) = self.get_all_quality_metadata(&item_quality) {
+            for (key, value) in metadata {
+                item.set_metadata(key, value.clone());
+            }
+        }
 
         // Attach subtype metadata to the item
         if let Some(metadata) = self.get_all_subtype_metadata(&item_type, &subtype) {
@@ -811,35 +2909,237 @@ impl PraedaGenerator {
             }
         }
 
+        // Attach metadata granted by the applied affixes, prefix first
+        let affix_metadata: Vec<(String, serde_json::Value)> = [item.get_prefix(), item.get_suffix()]
+            .iter()
+            .flat_map(|affix| affix.get_all_metadata())
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        for (key, value) in affix_metadata {
+            item.set_metadata(&key, value);
+        }
+
+        // Append the flavor text of the applied affixes, prefix first
+        let description = [
+            item.get_description(),
+            item.get_prefix().get_description(),
+            item.get_suffix().get_description(),
+        ]
+        .iter()
+        .filter(|text| !text.is_empty())
+        .copied()
+        .collect::<Vec<&str>>()
+        .join(" ");
+        item.set_description(description);
+
         Ok(item)
     }
 
-    fn calculate_attributes(
+    /// Picks an affix count from the configured distribution
+    fn roll_affix_count<R: Rng>(&self, rng: &mut R) -> Result<u32> {
+        let total_weight: i32 = self
+            .affix_count_distribution
+            .iter()
+            .map(|(_, weight)| (*weight).max(0))
+            .sum();
+        if total_weight <= 0 {
+            return Err(PraedaError::InvalidData(
+                "Affix count distribution has no positive weights".to_string(),
+            ));
+        }
+
+        let mut roll = rng.random_range(0..total_weight);
+        for (count, weight) in &self.affix_count_distribution {
+            roll -= (*weight).max(0);
+            if roll < 0 {
+                return Ok(*count);
+            }
+        }
+
+        // LCOV_EXCL_LINE - Unreachable code: the roll is always below the total weight
+        Err(PraedaError::InvalidData("Failed to select an affix count".to_string()))
+    }
+
+    /// Narrows an affix pool to the affixes whose power cost fits in `remaining`
+    fn affordable_affixes<'a>(pool: &[&'a Affix], remaining: f64) -> Vec<&'a Affix> {
+        pool.iter().copied().filter(|a| a.get_power_cost() <= remaining).collect()
+    }
+
+    /// Picks one affix from a non-empty pool. Uniform by default; with `level_weighted`, each
+    /// affix is weighted by `1 / (1 + |level - min_level|)` so affixes near the item level win
+    /// more often.
+    fn pick_affix<R: Rng>(pool: &[&Affix], level: f64, level_weighted: bool, rng: &mut R) -> Affix {
+        if !level_weighted {
+            return pool[rng.random_range(0..pool.len())].clone();
+        }
+
+        let weights: Vec<f64> = pool
+            .iter()
+            .map(|affix| 1.0 / (1.0 + (level - affix.min_level).abs()))
+            .collect();
+        let mut roll = rng.random::<f64>() * weights.iter().sum::<f64>();
+        for (affix, weight) in pool.iter().zip(&weights) {
+            if roll < *weight {
+                return (*affix).clone();
+            }
+            roll -= weight;
+        }
+        pool[pool.len() - 1].clone()
+    }
+
+    /// Collects the prefixes and suffixes that can apply to a type/subtype, merged from the
+    /// global, type-wide, subtype-wide and exact type/subtype pools. With `strict` only the
+    /// type-wide and exact pools are used. When `level` is given, affixes with a higher
+    /// `min_level` are left out, and when `quality` is given, affixes of a tier the quality
+    /// does not unlock are left out. Slots disallowed for the type via
+    /// [`set_affix_slots`](Self::set_affix_slots) yield an empty pool.
+    fn affix_pools(
+        &self,
+        item_type: &str,
+        subtype: &str,
+        strict: bool,
+        level: Option<f64>,
+        quality: Option<&str>,
+    ) -> (Vec<&Affix>, Vec<&Affix>) {
+        let valid_keys: Vec<(String, String)> = if strict {
+            vec![
+                (item_type.to_string(), "".to_string()),
+                (item_type.to_string(), subtype.to_string()),
+            ]
+        } else {
+            vec![
+                ("".to_string(), "".to_string()),
+                (item_type.to_string(), "".to_string()),
+                ("".to_string(), subtype.to_string()),
+                (item_type.to_string(), subtype.to_string()),
+            ]
+        };
+
+        let mut valid_prefixes: Vec<&Affix> = Vec::new();
+        let mut valid_suffixes: Vec<&Affix> = Vec::new();
+
+        for key in &valid_keys {
+            if let Some((prefixes, suffixes)) = self.item_affixes.get(key) {
+                valid_prefixes.extend(prefixes.iter());
+                valid_suffixes.extend(suffixes.iter());
+            }
+        }
+
+        if let Some(level) = level {
+            valid_prefixes.retain(|affix| affix.min_level <= level);
+            valid_suffixes.retain(|affix| affix.min_level <= level);
+        }
+
+        if let Some(quality) = quality {
+            let max_tier = self.get_quality_affix_tier(quality);
+            valid_prefixes.retain(|affix| affix.tier <= max_tier);
+            valid_suffixes.retain(|affix| affix.tier <= max_tier);
+        }
+
+        let (allow_prefix, allow_suffix) = self.get_affix_slots(item_type);
+        if !allow_prefix {
+            valid_prefixes.clear();
+        }
+        if !allow_suffix {
+            valid_suffixes.clear();
+        }
+
+        // Sort by name so seeded runs pick the same affixes regardless of insertion order
+        valid_prefixes.sort_by(|a, b| a.name.cmp(&b.name));
+        valid_suffixes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        (valid_prefixes, valid_suffixes)
+    }
+
+    /// Picks a name from the requested locale's list if one exists, else the quality-specific
+    /// list, else the general list for the type/subtype, else falls back to the configured
+    /// [`NameFallback`].
+    fn select_item_name<R: Rng>(
+        &self,
+        item_type: &str,
+        subtype: &str,
+        quality: &str,
+        locale: Option<&str>,
+        rng: &mut R,
+        name_deck: Option<&mut NameDeck>,
+    ) -> Result<String> {
+        let localized = locale.and_then(|locale| {
+            self.localized_item_list
+                .get(&(item_type.to_string(), subtype.to_string(), locale.to_string()))
+                .filter(|names| !names.is_empty())
+                .map(|names| (format!("locale:{}", locale), names))
+        });
+        let quality_key = (item_type.to_string(), subtype.to_string(), quality.to_string());
+        let names = localized
+            .or_else(|| {
+                self.quality_item_list
+                    .get(&quality_key)
+                    .filter(|names| !names.is_empty())
+                    .map(|names| (format!("quality:{}", quality), names))
+            })
+            .or_else(|| {
+                self.item_list
+                    .get(&(item_type.to_string(), subtype.to_string()))
+                    .map(|names| (String::new(), names))
+            });
+
+        match names {
+            Some((pool, names)) if !names.is_empty() => {
+                // Sort so seeded runs pick the same name regardless of insertion order
+                let mut sorted_names: Vec<&String> = names.iter().collect();
+                sorted_names.sort();
+                match name_deck {
+                    Some(deck) => {
+                        // Draw without replacement, reshuffling once the pool is exhausted
+                        let key = (item_type.to_string(), subtype.to_string(), pool);
+                        if deck.pools.get(&key).is_some_and(|names| names.is_empty()) {
+                            deck.refills += 1;
+                        }
+                        let remaining = deck.pools.entry(key).or_default();
+                        if remaining.is_empty() {
+                            remaining.extend(sorted_names.into_iter().cloned());
+                            remaining.shuffle(rng);
+                        }
+                        Ok(remaining.pop().unwrap_or_default())
+                    }
+                    None => Ok(sorted_names[rng.random_range(0..sorted_names.len())].clone()),
+                }
+            }
+            Some(_) if self.require_names => Err(PraedaError::EmptyItemNames(
+                item_type.to_string(),
+                subtype.to_string(),
+            )),
+            None if self.require_names => Err(PraedaError::MissingItemNames(
+                item_type.to_string(),
+                subtype.to_string(),
+            )),
+            _ => Ok(self.name_fallback.name_for(item_type, subtype)),
+        }
+    }
+
+    fn calculate_attributes<R: Rng>(
         &self,
         item: &mut Item,
         options: &GeneratorOptions,
-        rng: &mut rand::rngs::ThreadRng,
+        generated_level: f64,
+        rng: &mut R,
     ) -> Result<()> {
-        // Generate item level
-        let level_range = options.level_variance;
-        let generated_level = rng.random_range(
-            (options.base_level - level_range) as i32..=(options.base_level + level_range) as i32,
-        ) as f64;
-
+        item.set_level_attribute(options.level_attribute_name.clone());
         item.set_attribute(
-            "level",
+            &options.level_attribute_name,
             ItemAttribute::new(
-                "level",
+                &options.level_attribute_name,
                 generated_level,
                 0.0,
                 0.0,
                 false,
             ),
         );
+        item.set_attribute_source(&options.level_attribute_name, BASE_ATTRIBUTE_SOURCE, generated_level);
 
         // Collect valid attribute keys to check
         // LCOV_EXCL_START - Complex collection initialization rarely fully tested
-        let attribute_keys = vec![
+        let attribute_keys = [
             ("".to_string(), "".to_string()),
             (item.get_type().to_string(), "".to_string()),
             ("".to_string(), item.get_subtype().to_string()),
@@ -847,132 +3147,381 @@ impl PraedaGenerator {
         ];
         // LCOV_EXCL_END
 
-        let mut optional_attributes = Vec::new();
+        // An attribute overridden at a more specific key hides inherited ones of the same name
+        let overridden_below = |index: usize, name: &str| {
+            attribute_keys[index + 1..].iter().any(|key| {
+                self.attribute_overrides
+                    .get(key)
+                    .is_some_and(|names| names.contains(name))
+            })
+        };
+
+        let mut candidates: Vec<&ItemAttribute> = Vec::new();
+        for (index, key) in attribute_keys.iter().enumerate() {
+            let quality_key = (item.get_quality().to_string(), key.0.clone(), key.1.clone());
+            let attribute_lists = [
+                self.item_attributes.get(key),
+                self.quality_attributes.get(&quality_key),
+            ];
+            for attributes in attribute_lists.into_iter().flatten() {
+                candidates.extend(
+                    attributes
+                        .iter()
+                        .filter(|attr| !overridden_below(index, &attr.name)),
+                );
+            }
+        }
+        // Roll in name order so the same seed gives the same item however the configuration
+        // was built. The sort is stable, so a more specific key still wins for a shared name.
+        candidates.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut optional_attributes: Vec<&ItemAttribute> = Vec::new();
 
         // Process required attributes
         // LCOV_EXCL_START - Attribute processing with multiple conditional branches
-        for key in &attribute_keys {
-            if let Some(attributes) = self.item_attributes.get(key) {
-                for attr in attributes {
-                    if attr.get_required() {
-                        let mut new_attr = attr.clone();
-                        if attr.get_name().contains("_requirement") {
-                            new_attr.set_initial_value(generated_level);
-                        } else {
-                            new_attr.generate_value(
-                                generated_level,
-                                options.linear,
-                                options.scaling_factor,
-                            );
-                        }
-                        item.set_attribute(&attr.name, new_attr);
-                    } else {
-                        optional_attributes.push(attr.clone());
-                    }
+        for attr in candidates {
+            if attr.get_required() {
+                let mut new_attr = attr.clone();
+                if is_requirement_attribute(attr) {
+                    new_attr.set_initial_value(generated_level);
+                } else if attr.rolls_range() {
+                    new_attr.roll_value(rng);
+                } else {
+                    new_attr.generate_value_with_curve(
+                        generated_level,
+                        attr.get_curve().unwrap_or(options.scaling_curve()),
+                        options.scaling_factor,
+                    );
                 }
+                ensure_finite(item, &new_attr, BASE_ATTRIBUTE_SOURCE)?;
+                item.set_attribute_source(
+                    &attr.name,
+                    BASE_ATTRIBUTE_SOURCE,
+                    new_attr.initial_value,
+                );
+                item.set_attribute(&attr.name, new_attr);
+            } else {
+                optional_attributes.push(attr);
             }
         }
         // LCOV_EXCL_END
 
-        // Process optional attributes with affix chance
-        #[cfg(not(tarpaulin_include))]
-        {
-            for attr in optional_attributes {
-                if rng.random::<f64>() <= options.affix_chance {
-                    let mut final_attr = if let Some(existing) = item.get_attribute(&attr.name) {
-                        let mut new_attr = existing.clone();
-                        new_attr.initial_value += attr.initial_value;
-                        new_attr
-                    } else {
-                        let mut new_attr = attr.clone();
-                        if !new_attr.get_name().contains("_requirement") {
-                            new_attr.generate_value(
-                                generated_level,
-                                options.linear,
-                                options.scaling_factor,
-                            );
-                        }
-                        new_attr
-                    };
+        // Process optional attributes with the same level-scaled chance as affixes
+        let affix_chance = options.affix_chance_at_level(generated_level);
+        for attr in optional_attributes {
+            if rng.random::<f64>() <= affix_chance {
+                self.apply_attribute(
+                    item,
+                    attr,
+                    BASE_ATTRIBUTE_SOURCE,
+                    true,
+                    generated_level,
+                    options,
+                    rng,
+                )?;
+            }
+        }
 
-                    if final_attr.get_name().contains("_requirement") {
-                        final_attr.set_initial_value(generated_level);
-                    }
+        // Process attribute groups, picking a fixed number from each instead of rolling chance
+        for key in &attribute_keys {
+            for group in self.attribute_groups.get(key).into_iter().flatten() {
+                let attributes = group.get_attributes();
+                let pick = group.get_pick().min(attributes.len());
+                let mut picked = rand::seq::index::sample(rng, attributes.len(), pick).into_vec();
+                // Apply in group order so the breakdown does not depend on the draw order
+                picked.sort_unstable();
+                for index in picked {
+                    let attr = &attributes[index];
+                    self.apply_attribute(
+                        item,
+                        attr,
+                        BASE_ATTRIBUTE_SOURCE,
+                        true,
+                        generated_level,
+                        options,
+                        rng,
+                    )?;
+                }
+            }
+        }
 
-                    item.set_attribute(&attr.name, final_attr);
+        // Apply prefix and suffix attributes. The affixes are moved out of the item while
+        // their attributes are merged so they can be borrowed instead of cloned. Duplicate
+        // names within an affix are collapsed first so an attribute is never applied twice.
+        let mut prefix = std::mem::replace(item.get_prefix_mut(), Affix::empty());
+        prefix.dedup_attributes();
+        let mut suffix = std::mem::replace(item.get_suffix_mut(), Affix::empty());
+        suffix.dedup_attributes();
+
+        // Additive attributes first, then multiplicative ones against the summed value
+        for mode in [ApplyMode::Add, ApplyMode::Multiply] {
+            for affix in [&prefix, &suffix] {
+                for attr in affix
+                    .get_attributes()
+                    .iter()
+                    .filter(|a| a.apply_mode == mode)
+                {
+                    self.apply_attribute(
+                        item,
+                        attr,
+                        affix.get_name(),
+                        false,
+                        generated_level,
+                        options,
+                        rng,
+                    )?;
                 }
             }
         }
+        item.set_prefix(prefix);
+        item.set_suffix(suffix);
 
-        // Apply prefix attributes
-        #[cfg(not(tarpaulin_include))]
-        {
-            let prefix_attributes = item.get_prefix().get_attributes().to_vec();
-            for prefix_attr in prefix_attributes {
-                let mut final_attr = if let Some(existing) = item.get_attribute(&prefix_attr.name) {
-                    let mut new_attr = existing.clone();
-                    new_attr.initial_value += prefix_attr.initial_value;
-                    new_attr
-                } else {
-                    prefix_attr.clone()
-                };
+        Ok(())
+    }
 
-                if final_attr.get_name().contains("_requirement") {
-                    final_attr.set_initial_value(generated_level);
-                }
+    /// Merges a single optional or affix attribute into an item.
+    ///
+    /// If the item already has the attribute, `attr.initial_value` is added to it (or multiplies
+    /// it, for [`ApplyMode::Multiply`]) and the contribution is clamped to the existing
+    /// attribute's bounds. A level-scaled value may already lie outside them, so the clamp never
+    /// moves the result past the pre-merge value: a positive contribution cannot lower it and a
+    /// negative one cannot raise it. Otherwise `attr` is inserted, scaled by level first when `scale_new` is set; a
+    /// multiplicative attribute with nothing to multiply is skipped. A range attribute (see
+    /// [`ItemAttribute::rolls_range`]) contributes a fresh unscaled roll instead. The total is then raised to
+    /// [`GeneratorOptions::attribute_floor`] if needed. Requirement attributes always end up at
+    /// the generated level. Every change is recorded in the item's attribute breakdown under
+    /// `source`, with any clamping recorded under [`CLAMP_ATTRIBUTE_SOURCE`].
+    ///
+    /// Returns [`PraedaError::InvalidData`] if the merged value is not finite, e.g. when a
+    /// multiplier overflows an unbounded attribute.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_attribute<R: Rng>(
+        &self,
+        item: &mut Item,
+        attr: &ItemAttribute,
+        source: &str,
+        scale_new: bool,
+        generated_level: f64,
+        options: &GeneratorOptions,
+        rng: &mut R,
+    ) -> Result<()> {
+        // Range attributes contribute a fresh roll instead of their (zero) initial value
+        let rolled;
+        let (attr, scale_new) = if attr.rolls_range() {
+            let mut roll = attr.clone();
+            roll.roll_value(rng);
+            rolled = roll;
+            (&rolled, false)
+        } else {
+            (attr, scale_new)
+        };
 
-                item.set_attribute(&prefix_attr.name, final_attr);
+        let pre_merge = item.get_attribute(&attr.name).map(|existing| existing.initial_value);
+        let mut final_attr = if let Some(existing) = item.get_attribute(&attr.name) {
+            let mut new_attr = existing.clone();
+            let contribution = match attr.apply_mode {
+                ApplyMode::Add => attr.initial_value,
+                ApplyMode::Multiply => existing.initial_value * (attr.initial_value - 1.0),
+            };
+            new_attr.initial_value += contribution;
+            item.add_attribute_source(&attr.name, source, contribution);
+            new_attr
+        } else if attr.apply_mode == ApplyMode::Multiply {
+            // Nothing to multiply
+            return Ok(());
+        } else {
+            let mut new_attr = attr.clone();
+            if scale_new && !is_requirement_attribute(&new_attr) {
+                new_attr.generate_value_with_curve(
+                    generated_level,
+                    attr.get_curve().unwrap_or(options.scaling_curve()),
+                    options.scaling_factor,
+                );
             }
+            item.set_attribute_source(&attr.name, source, new_attr.initial_value);
+            new_attr
+        };
 
-            // Apply suffix attributes
-            let suffix_attributes = item.get_suffix().get_attributes().to_vec();
-            for suffix_attr in suffix_attributes {
-                let mut final_attr = if let Some(existing) = item.get_attribute(&suffix_attr.name) {
-                    let mut new_attr = existing.clone();
-                    new_attr.initial_value += suffix_attr.initial_value;
-                    new_attr
-                } else {
-                    suffix_attr.clone()
-                };
+        let unclamped = final_attr.initial_value;
 
-                if final_attr.get_name().contains("_requirement") {
-                    final_attr.set_initial_value(generated_level);
-                }
+        // The contribution must stay within the existing attribute's bounds, but a scaled
+        // value already outside them is left where it was rather than pulled back
+        if let Some(pre_merge) = pre_merge
+            && final_attr.has_bounds()
+        {
+            let lower = final_attr.min.min(pre_merge);
+            let upper = final_attr.max.max(pre_merge);
+            final_attr.initial_value = final_attr.initial_value.clamp(lower, upper);
+        }
 
-                item.set_attribute(&suffix_attr.name, final_attr);
+        // Negative contributions (e.g. cursed affixes) may not push the total below the floor
+        if let Some(floor) = options.attribute_floor {
+            let floor = if final_attr.has_bounds() { floor.max(final_attr.min) } else { floor };
+            if final_attr.initial_value < floor {
+                final_attr.initial_value = floor;
             }
         }
 
+        ensure_finite(item, &final_attr, source)?;
+
+        if final_attr.initial_value != unclamped {
+            item.add_attribute_source(
+                &attr.name,
+                CLAMP_ATTRIBUTE_SOURCE,
+                final_attr.initial_value - unclamped,
+            );
+        }
+
+        if is_requirement_attribute(&final_attr) {
+            final_attr.set_initial_value(generated_level);
+            item.set_attribute_source(&attr.name, BASE_ATTRIBUTE_SOURCE, generated_level);
+        }
+
+        item.set_attribute(&attr.name, final_attr);
         Ok(())
     }
 
-    fn weighted_random_select(
+    /// Quality weights with rarer tiers boosted by `luck`, as fractional weights.
+    ///
+    /// Distinct weights are ranked from most common (rank 0) to rarest; each quality's weight is
+    /// multiplied by `1 + luck * rank / max_rank`. The result is kept in f64 so large weights
+    /// cannot overflow once boosted.
+    fn luck_adjusted_quality_weights(&self, luck: f64) -> HashMap<String, f64> {
+        let ranks = luck_ranks(self.quality_data.values().map(|&weight| weight as f64));
+
+        self.quality_data
+            .iter()
+            .map(|(quality, &weight)| {
+                let weight = weight as f64;
+                (quality.clone(), weight * ranks.multiplier(weight, luck))
+            })
+            .collect()
+    }
+
+    /// Fractional quality weights with rarer tiers boosted by `luck`, as in
+    /// [`luck_adjusted_quality_weights`](Self::luck_adjusted_quality_weights)
+    fn luck_adjusted_quality_weights_f64(&self, luck: f64) -> Cow<'_, HashMap<String, f64>> {
+        if luck == 0.0 {
+            return Cow::Borrowed(&self.quality_data_f64);
+        }
+
+        let ranks = luck_ranks(self.quality_data_f64.values().copied());
+
+        let adjusted = self
+            .quality_data_f64
+            .iter()
+            .map(|(quality, &weight)| (quality.clone(), weight * ranks.multiplier(weight, luck)))
+            .collect();
+
+        Cow::Owned(adjusted)
+    }
+
+    /// Picks a key with probability proportional to its weight.
+    ///
+    /// Rolls uniformly in `0..total_weight` and maps the roll with
+    /// [`weighted_key_for_roll`](Self::weighted_key_for_roll), so the same seed always selects
+    /// the same key, independent of `HashMap` iteration order or configuration insertion order.
+    fn weighted_random_select<R: Rng>(
         &self,
         weights: &HashMap<String, i32>,
-        rng: &mut rand::rngs::ThreadRng,
+        rng: &mut R,
     ) -> Result<String> {
         if weights.is_empty() {
             return Err(PraedaError::InvalidData("No items to select from".to_string()));
         }
 
-        let total_weight: i32 = weights.values().sum();
-        let mut roll = rng.random_range(0..total_weight);
+        let total_weight = weights
+            .values()
+            .try_fold(0i32, |total, &weight| total.checked_add(weight))
+            .ok_or_else(|| PraedaError::InvalidData("Total weight overflows i32".to_string()))?;
+        if total_weight <= 0 {
+            return Err(PraedaError::InvalidData(
+                "Total weight must be positive to select from".to_string(),
+            ));
+        }
+        let roll = rng.random_range(0..total_weight);
+
+        // LCOV_EXCL_LINE - Unreachable error: every roll below the total weight maps to a key
+        Self::weighted_key_for_roll(weights, roll)
+            .map(str::to_string)
+            .ok_or_else(|| PraedaError::InvalidData("Failed to select from weights".to_string()))
+    }
+
+    /// Maps a roll in `0..total_weight` to the key it selects in a weighted pick.
+    ///
+    /// Keys are walked in sorted order, each covering a half-open span of the roll range as
+    /// wide as its weight: with `{"a": 2, "b": 3}`, rolls 0-1 select `"a"` and rolls 2-4 select
+    /// `"b"`. Keys with a weight of 0 or less never cover a roll. Returns `None` for a negative
+    /// roll or a roll at or beyond the total weight.
+    fn weighted_key_for_roll(weights: &HashMap<String, i32>, roll: i32) -> Option<&str> {
+        if roll < 0 {
+            return None;
+        }
 
         // Sort keys to ensure deterministic iteration order
         let mut sorted_keys: Vec<_> = weights.keys().collect();
         sorted_keys.sort();
 
+        let mut remaining = roll;
         for key in sorted_keys {
-            roll -= weights[key];
-            if roll < 0 {
-                return Ok(key.clone());
+            remaining -= weights[key];
+            if remaining < 0 {
+                return Some(key.as_str());
+            }
+        }
+        None
+    }
+
+    /// Picks a key with probability proportional to its fractional weight.
+    ///
+    /// Keys are sorted before rolling, as in [`weighted_random_select`](Self::weighted_random_select).
+    fn weighted_random_select_f64<R: Rng>(
+        &self,
+        weights: &HashMap<String, f64>,
+        rng: &mut R,
+    ) -> Result<String> {
+        let total_weight: f64 = weights.values().filter(|w| **w > 0.0).sum();
+        if total_weight <= 0.0 {
+            return Err(PraedaError::InvalidData("No items to select from".to_string()));
+        }
+
+        let mut roll = rng.random::<f64>() * total_weight;
+
+        let mut sorted_keys: Vec<_> = weights.keys().filter(|key| weights[*key] > 0.0).collect();
+        sorted_keys.sort();
+
+        for key in &sorted_keys {
+            roll -= weights[*key];
+            if roll < 0.0 {
+                return Ok((*key).clone());
             }
         }
 
-        // Fallback to last item if rounding error (should never reach here)
-        // LCOV_EXCL_LINE - Unreachable code: algorithm always returns in loop above
-        Err(PraedaError::InvalidData("Failed to select from weights".to_string()))
+        // Floating point rounding can leave a tiny remainder; it belongs to the last key
+        Ok(sorted_keys[sorted_keys.len() - 1].clone())
+    }
+}
+
+/// Rejects an attribute value that is NaN or infinite, naming the source that produced it
+fn ensure_finite(item: &Item, attr: &ItemAttribute, source: &str) -> Result<()> {
+    if attr.initial_value.is_finite() {
+        return Ok(());
     }
+    Err(PraedaError::InvalidData(format!(
+        "Attribute '{}' on item '{}' is not finite after applying '{}'",
+        attr.name,
+        item.get_name(),
+        source
+    )))
+}
+
+/// Requirement attributes take the generated item level as their value instead of being scaled.
+///
+/// Attributes are flagged explicitly through [`ItemAttribute::is_requirement`]. Names ending in
+/// `_requirement` are still treated as requirements as a deprecated fallback for configurations
+/// written before the flag existed.
+fn is_requirement_attribute(attr: &ItemAttribute) -> bool {
+    attr.get_is_requirement() || attr.get_name().ends_with("_requirement")
 }
 
 impl Default for PraedaGenerator {
@@ -980,3 +3529,84 @@ impl Default for PraedaGenerator {
         Self::new()
     }
 }
+
+/// Distinct quality weights ranked from most common (rank 0) to rarest, used to scale luck
+struct LuckRanks {
+    distinct_weights: Vec<f64>,
+}
+
+impl LuckRanks {
+    /// Luck multiplier for a weight: `1 + luck * rank / max_rank`, never below zero
+    fn multiplier(&self, weight: f64, luck: f64) -> f64 {
+        let max_rank = self.distinct_weights.len().saturating_sub(1).max(1) as f64;
+        let rank = self.distinct_weights.iter().position(|&w| w == weight).unwrap_or(0) as f64;
+        (1.0 + luck * rank / max_rank).max(0.0)
+    }
+}
+
+/// Restricts quality weights to a whitelist; an empty whitelist keeps every quality.
+///
+/// Fails if the whitelist leaves nothing with a positive weight to roll.
+fn whitelist_qualities<'a, W: Copy + Into<f64>>(
+    weights: Cow<'a, HashMap<String, W>>,
+    whitelist: &[String],
+) -> Result<Cow<'a, HashMap<String, W>>> {
+    if whitelist.is_empty() {
+        return Ok(weights);
+    }
+
+    let allowed: HashMap<String, W> = weights
+        .iter()
+        .filter(|(quality, _)| whitelist.contains(quality))
+        .map(|(quality, weight)| (quality.clone(), *weight))
+        .collect();
+    if allowed.is_empty() {
+        return Err(PraedaError::InvalidData(format!(
+            "Quality whitelist {:?} matches no configured quality",
+            whitelist
+        )));
+    }
+    let total_weight: f64 = allowed.values().map(|&weight| weight.into()).filter(|w| *w > 0.0).sum();
+    if total_weight <= 0.0 {
+        return Err(PraedaError::InvalidData(format!(
+            "Quality whitelist {:?} leaves no quality with a positive weight",
+            whitelist
+        )));
+    }
+    Ok(Cow::Owned(allowed))
+}
+
+fn luck_ranks(weights: impl Iterator<Item = f64>) -> LuckRanks {
+    let mut distinct_weights: Vec<f64> = weights.collect();
+    distinct_weights.sort_unstable_by(|a, b| b.total_cmp(a));
+    distinct_weights.dedup();
+    LuckRanks { distinct_weights }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_key_for_roll_boundaries() {
+        let weights: HashMap<String, i32> = [
+            ("rare".to_string(), 3),
+            ("common".to_string(), 5),
+            ("legendary".to_string(), 1),
+            ("cursed".to_string(), 0),
+        ]
+        .into_iter()
+        .collect();
+
+        // Sorted order: common (0-4), cursed (never), legendary (5), rare (6-8)
+        let pick = |roll| PraedaGenerator::weighted_key_for_roll(&weights, roll);
+        assert_eq!(pick(0), Some("common"));
+        assert_eq!(pick(4), Some("common"));
+        assert_eq!(pick(5), Some("legendary"));
+        assert_eq!(pick(6), Some("rare"));
+        assert_eq!(pick(8), Some("rare"));
+        assert_eq!(pick(9), None);
+        assert_eq!(pick(-1), None);
+        assert!((0..9).all(|roll| pick(roll) != Some("cursed")));
+    }
+}
@@ -37,6 +37,7 @@
 //!     affix_chance: 0.25,
 //!     linear: true,
 //!     scaling_factor: 1.5,
+//!     ..Default::default()
 //! };
 //!
 //! let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "main")?;
@@ -1,5 +1,9 @@
+use crate::error::{PraedaError, Result};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Represents an item type with subtypes and weight
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -154,6 +158,17 @@ impl ItemData {
 /// * `required` - If true, this attribute is always applied; if false, it depends on chance
 /// * `scaling_factor` - Multiplier applied per level (linear: adds, exponential: multiplies)
 /// * `chance` - Probability (0.0-1.0) of being included if not required
+/// * `is_requirement` - If true, the value is set to the generated item level instead of being scaled
+/// * `curve` - [`ScalingCurve`] for this attribute, overriding the generation-wide curve. `None`
+///   inherits [`GeneratorOptions::scaling_curve`]
+/// * `format` - [`AttributeFormat`] hint for displaying the value (default `Flat`)
+/// * `apply_mode` - [`ApplyMode`] used when this attribute is merged into an item that already
+///   has it (default `Add`)
+/// * `roll_range` - If true and `initial_value` is 0.0 with `min < max`, the value is rolled
+///   uniformly in `min..=max` instead of being scaled by level (default `false`)
+/// * `min_per_level` / `max_per_level` - Added to `min` / `max` per level when the value is
+///   scaled, so caps can grow with level. When either is non-zero the scaled value is clamped
+///   to the level's bounds; both default to 0.0, which leaves scaled values unclamped
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ItemAttribute {
     pub name: String,
@@ -165,6 +180,20 @@ pub struct ItemAttribute {
     pub scaling_factor: f64,
     #[serde(default)]
     pub chance: f64,
+    #[serde(default)]
+    pub is_requirement: bool,
+    #[serde(default)]
+    pub curve: Option<ScalingCurve>,
+    #[serde(default)]
+    pub format: AttributeFormat,
+    #[serde(default)]
+    pub apply_mode: ApplyMode,
+    #[serde(default)]
+    pub roll_range: bool,
+    #[serde(default)]
+    pub min_per_level: f64,
+    #[serde(default)]
+    pub max_per_level: f64,
 }
 
 impl ItemAttribute {
@@ -192,9 +221,31 @@ impl ItemAttribute {
             required,
             scaling_factor: 1.0,
             chance: 0.0,
+            is_requirement: false,
+            curve: None,
+            format: AttributeFormat::Flat,
+            apply_mode: ApplyMode::Add,
+            roll_range: false,
+            min_per_level: 0.0,
+            max_per_level: 0.0,
         }
     }
 
+    /// Creates a requirement attribute (e.g., a strength or level requirement).
+    ///
+    /// Requirement attributes are not scaled; during generation their value is set to the
+    /// generated item level.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the attribute
+    /// * `required` - If true, always applied; if false, chance-based
+    pub fn new_requirement(name: &str, required: bool) -> Self {
+        let mut attribute = ItemAttribute::new(name, 0.0, 0.0, 0.0, required);
+        attribute.is_requirement = true;
+        attribute
+    }
+
     pub fn set_name(&mut self, name: String) {
         self.name = name;
     }
@@ -241,21 +292,163 @@ impl ItemAttribute {
         self.required
     }
 
+    pub fn set_scaling_factor(&mut self, scaling_factor: f64) {
+        self.scaling_factor = scaling_factor;
+    }
+
+    pub fn get_scaling_factor(&self) -> f64 {
+        self.scaling_factor
+    }
+
+    pub fn set_chance(&mut self, chance: f64) {
+        self.chance = chance;
+    }
+
+    pub fn get_chance(&self) -> f64 {
+        self.chance
+    }
+
+    pub fn set_is_requirement(&mut self, is_requirement: bool) {
+        self.is_requirement = is_requirement;
+    }
+
+    pub fn get_is_requirement(&self) -> bool {
+        self.is_requirement
+    }
+
+    pub fn set_curve(&mut self, curve: Option<ScalingCurve>) {
+        self.curve = curve;
+    }
+
+    pub fn get_curve(&self) -> Option<ScalingCurve> {
+        self.curve
+    }
+
+    pub fn set_format(&mut self, format: AttributeFormat) {
+        self.format = format;
+    }
+
+    pub fn get_format(&self) -> AttributeFormat {
+        self.format
+    }
+
+    pub fn set_apply_mode(&mut self, apply_mode: ApplyMode) {
+        self.apply_mode = apply_mode;
+    }
+
+    pub fn get_apply_mode(&self) -> ApplyMode {
+        self.apply_mode
+    }
+
+    pub fn set_roll_range(&mut self, roll_range: bool) {
+        self.roll_range = roll_range;
+    }
+
+    pub fn get_roll_range(&self) -> bool {
+        self.roll_range
+    }
+
+    pub fn set_min_per_level(&mut self, min_per_level: f64) {
+        self.min_per_level = min_per_level;
+    }
+
+    pub fn get_min_per_level(&self) -> f64 {
+        self.min_per_level
+    }
+
+    pub fn set_max_per_level(&mut self, max_per_level: f64) {
+        self.max_per_level = max_per_level;
+    }
+
+    pub fn get_max_per_level(&self) -> f64 {
+        self.max_per_level
+    }
+
+    /// Whether this attribute is a pure random roll over `min..=max`: `roll_range` is set, the
+    /// initial value is 0.0, `min < max`, and it is not a requirement attribute
+    pub fn rolls_range(&self) -> bool {
+        self.roll_range && self.initial_value == 0.0 && self.min < self.max && !self.is_requirement
+    }
+
+    /// Sets the initial value to a uniform roll in `min..=max`, with no level scaling
+    pub fn roll_value<R: Rng>(&mut self, rng: &mut R) {
+        self.initial_value = rng.random_range(self.min..=self.max);
+    }
+
+    /// Renders the initial value for display according to `format`, e.g. `15` or `15%`.
+    ///
+    /// Values are rounded to two decimal places and trailing zeros are dropped.
+    pub fn format_value(&self) -> String {
+        let value = (self.initial_value * 100.0).round() / 100.0;
+        match self.format {
+            AttributeFormat::Flat => format!("{}", value),
+            AttributeFormat::Percent => format!("{}%", value),
+            AttributeFormat::PerSecond => format!("{}/s", value),
+            AttributeFormat::Duration => format!("{}s", value),
+        }
+    }
+
+    /// Whether `min` and `max` describe a usable range. Attributes created with both bounds at
+    /// zero (or with `min >= max`) are treated as unbounded.
+    pub fn has_bounds(&self) -> bool {
+        self.min < self.max
+    }
+
+    /// Clamp the value to `[min, max]` when the attribute has meaningful bounds
+    pub fn clamp_to_bounds(&mut self) {
+        if self.has_bounds() {
+            self.initial_value = self.initial_value.clamp(self.min, self.max);
+        }
+    }
+
     /// Generate a scaled value based on level, scaling factor, and linear/exponential progression
     pub fn generate_value(&mut self, new_level: f64, linear: bool, scaling_factor: f64) {
+        self.generate_value_with_curve(new_level, ScalingCurve::from_linear(linear), scaling_factor);
+    }
+
+    /// Generate a scaled value based on level, scaling factor, and a [`ScalingCurve`]
+    ///
+    /// With `min_per_level` or `max_per_level` set, the bounds are moved to
+    /// `min + min_per_level * level` and `max + max_per_level * level` and the value is
+    /// clamped to them.
+    pub fn generate_value_with_curve(&mut self, new_level: f64, curve: ScalingCurve, scaling_factor: f64) {
         if self.min == 0.0 && self.max == 0.0 && self.initial_value != 0.0 {
             self.min = self.initial_value;
             self.max = self.initial_value;
         }
 
-        if self.initial_value == 0.0 && !linear {
+        if self.initial_value == 0.0 && curve == ScalingCurve::Exponential {
             self.initial_value = 1.0;
         }
 
-        if linear {
-            self.initial_value += new_level * scaling_factor;
-        } else {
-            self.initial_value *= scaling_factor.powf(new_level);
+        match curve {
+            ScalingCurve::Linear => self.initial_value += new_level * scaling_factor,
+            ScalingCurve::Exponential => self.initial_value *= scaling_factor.powf(new_level),
+            ScalingCurve::Logarithmic => {
+                self.initial_value += scaling_factor * new_level.max(0.0).ln_1p();
+            }
+            ScalingCurve::Sigmoid { midpoint, steepness } => {
+                self.initial_value +=
+                    scaling_factor / (1.0 + (-steepness * (new_level - midpoint)).exp());
+            }
+        }
+
+        if self.min_per_level != 0.0 || self.max_per_level != 0.0 {
+            self.min += self.min_per_level * new_level;
+            self.max += self.max_per_level * new_level;
+            self.clamp_to_bounds();
+        }
+
+        // Extreme levels can overflow scaling in either direction; keep the value finite. A NaN
+        // (e.g. from a NaN scaling factor) has no bound to saturate to and is left for the
+        // generator to reject.
+        if !self.initial_value.is_finite() && !self.initial_value.is_nan() {
+            self.initial_value = match (self.initial_value > 0.0, self.has_bounds()) {
+                (true, true) => self.max,
+                (true, false) => f64::MAX,
+                (false, true) => self.min,
+                (false, false) => f64::MIN,
+            };
         }
 
         if self.initial_value < 0.0 {
@@ -273,21 +466,60 @@ impl ItemAttribute {
 ///
 /// A "Flaming" prefix might add extra fire damage to a weapon, implemented as an affix with a
 /// single damage attribute.
+///
+/// `min_level` keeps high-tier affixes off low-level items: the affix only rolls on items whose
+/// generated level is at least `min_level` (default 0.0).
+///
+/// `metadata` lets an affix flag behavior instead of (or as well as) changing stats, e.g. a
+/// "Soulbound" affix setting `soulbound = true`. It is merged into the item's metadata when the
+/// affix is applied.
+///
+/// `tier` is the affix's rarity tier (default 0). An affix of tier above 0 only rolls on items
+/// whose quality unlocks that tier, see [`PraedaGenerator::set_quality_affix_tier_min`].
+///
+/// [`PraedaGenerator::set_quality_affix_tier_min`]: crate::PraedaGenerator::set_quality_affix_tier_min
+///
+/// Attribute names are unique within an affix. [`set_attribute`](Self::set_attribute) replaces an
+/// existing attribute of the same name, and when an affix built from a list with duplicate names
+/// is applied to an item, the last attribute with each name wins.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Affix {
     pub name: String,
     pub attributes: Vec<ItemAttribute>,
+    #[serde(default)]
+    pub min_level: f64,
+    #[serde(default)]
+    pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub tier: u32,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub power_cost: Option<f64>,
 }
 
 impl Affix {
     pub fn new(name: &str, attributes: Vec<ItemAttribute>) -> Self {
-        Affix { name: name.to_string(), attributes }
+        Affix {
+            name: name.to_string(),
+            attributes,
+            min_level: 0.0,
+            metadata: HashMap::new(),
+            tier: 0,
+            description: String::new(),
+            power_cost: None,
+        }
     }
 
     pub fn empty() -> Self {
         Affix {
             name: String::new(),
             attributes: Vec::new(),
+            min_level: 0.0,
+            metadata: HashMap::new(),
+            tier: 0,
+            description: String::new(),
+            power_cost: None,
         }
     }
 
@@ -307,6 +539,68 @@ impl Affix {
         self.attributes = attributes;
     }
 
+    pub fn get_min_level(&self) -> f64 {
+        self.min_level
+    }
+
+    pub fn set_min_level(&mut self, min_level: f64) {
+        self.min_level = min_level;
+    }
+
+    pub fn get_metadata(&self, key: &str) -> Option<&serde_json::Value> {
+        self.metadata.get(key)
+    }
+
+    pub fn set_metadata(&mut self, key: &str, value: serde_json::Value) {
+        self.metadata.insert(key.to_string(), value);
+    }
+
+    pub fn get_all_metadata(&self) -> &HashMap<String, serde_json::Value> {
+        &self.metadata
+    }
+
+    pub fn get_tier(&self) -> u32 {
+        self.tier
+    }
+
+    pub fn set_tier(&mut self, tier: u32) {
+        self.tier = tier;
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn set_description(&mut self, description: String) {
+        self.description = description;
+    }
+
+    /// How much of a [`power_budget`](GeneratorOptions::power_budget) this affix consumes.
+    ///
+    /// Defaults to the sum of its attributes' initial values unless set explicitly.
+    pub fn get_power_cost(&self) -> f64 {
+        self.power_cost
+            .unwrap_or_else(|| self.attributes.iter().map(|a| a.get_initial_value()).sum())
+    }
+
+    pub fn set_power_cost(&mut self, power_cost: f64) {
+        self.power_cost = Some(power_cost);
+    }
+
+    /// Remove attributes that share a name, keeping the last one with each name in the position
+    /// of the first
+    pub fn dedup_attributes(&mut self) {
+        let mut deduped: Vec<ItemAttribute> = Vec::with_capacity(self.attributes.len());
+        for attribute in self.attributes.drain(..) {
+            if let Some(pos) = deduped.iter().position(|a| a.name == attribute.name) {
+                deduped[pos] = attribute;
+            } else {
+                deduped.push(attribute);
+            }
+        }
+        self.attributes = deduped;
+    }
+
     pub fn set_attribute(&mut self, new_attribute: ItemAttribute) {
         if let Some(pos) = self
             .attributes
@@ -320,6 +614,41 @@ impl Affix {
     }
 }
 
+/// A named set of optional attributes of which a fixed number roll on each item.
+///
+/// Unlike optional attributes, which each roll against the affix chance, exactly `pick` of a
+/// group's attributes are chosen uniformly at random for every item, or all of them if the
+/// group has fewer. See
+/// [`set_attribute_group`](crate::generator::PraedaGenerator::set_attribute_group).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttributeGroup {
+    pub name: String,
+    pub attributes: Vec<ItemAttribute>,
+    pub pick: usize,
+}
+
+impl AttributeGroup {
+    pub fn new(name: &str, attributes: Vec<ItemAttribute>, pick: usize) -> Self {
+        AttributeGroup {
+            name: name.to_string(),
+            attributes,
+            pick,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_attributes(&self) -> &[ItemAttribute] {
+        &self.attributes
+    }
+
+    pub fn get_pick(&self) -> usize {
+        self.pick
+    }
+}
+
 /// Represents a complete generated item.
 ///
 /// An `Item` is the output of the loot generation process. It contains all the information
@@ -334,7 +663,15 @@ impl Affix {
 /// * `prefix` - Prefix affix applied to this item (empty if none)
 /// * `suffix` - Suffix affix applied to this item (empty if none)
 /// * `attributes` - Map of attribute names to their values (damage, defense, etc.)
+/// * `attribute_sources` - Per attribute, the `(source, amount)` contributions that make up its
+///   value. The source is the name of the affix that contributed the amount, or one of the
+///   reserved sources [`BASE_ATTRIBUTE_SOURCE`](crate::generator::BASE_ATTRIBUTE_SOURCE) and
+///   [`CLAMP_ATTRIBUTE_SOURCE`](crate::generator::CLAMP_ATTRIBUTE_SOURCE)
 /// * `metadata` - Additional metadata (application-specific data)
+/// * `description` - Flavor text for tooltips. Generation appends the descriptions of the
+///   applied prefix and suffix, in that order, separated by spaces
+/// * `level_attribute` - Name of the attribute holding the item level, from
+///   [`GeneratorOptions::level_attribute_name`] (default `"level"`)
 ///
 /// # Example
 ///
@@ -359,7 +696,13 @@ pub struct Item {
     pub suffix: Affix,
     pub attributes: HashMap<String, ItemAttribute>,
     #[serde(default)]
+    pub attribute_sources: HashMap<String, Vec<(String, f64)>>,
+    #[serde(default)]
     pub metadata: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_level_attribute_name")]
+    pub level_attribute: String,
 }
 
 impl Item {
@@ -384,7 +727,10 @@ impl Item {
             prefix,
             suffix,
             attributes,
+            attribute_sources: HashMap::new(),
             metadata: HashMap::new(),
+            description: String::new(),
+            level_attribute: default_level_attribute_name(),
         }
     }
 
@@ -397,7 +743,10 @@ impl Item {
             prefix: Affix::empty(),
             suffix: Affix::empty(),
             attributes: HashMap::new(),
+            attribute_sources: HashMap::new(),
             metadata: HashMap::new(),
+            description: String::new(),
+            level_attribute: default_level_attribute_name(),
         }
     }
 
@@ -433,6 +782,22 @@ impl Item {
         &self.subtype
     }
 
+    pub fn set_description(&mut self, description: String) {
+        self.description = description;
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn set_level_attribute(&mut self, level_attribute: String) {
+        self.level_attribute = level_attribute;
+    }
+
+    pub fn get_level_attribute(&self) -> &str {
+        &self.level_attribute
+    }
+
     pub fn set_prefix(&mut self, prefix: Affix) {
         self.prefix = prefix;
     }
@@ -467,6 +832,17 @@ impl Item {
         &self.attributes
     }
 
+    /// Attributes sorted by name, for a stable display and serialization order
+    pub fn get_attributes_ordered(&self) -> Vec<(&str, &ItemAttribute)> {
+        let mut attributes: Vec<(&str, &ItemAttribute)> = self
+            .attributes
+            .iter()
+            .map(|(name, attr)| (name.as_str(), attr))
+            .collect();
+        attributes.sort_by(|a, b| a.0.cmp(b.0));
+        attributes
+    }
+
     pub fn set_attribute(&mut self, name: &str, attr: ItemAttribute) {
         self.attributes.insert(name.to_string(), attr);
     }
@@ -483,6 +859,41 @@ impl Item {
         self.attributes.get_mut(name)
     }
 
+    /// Final value of an attribute, with base and affix contributions already merged.
+    /// Returns 0.0 if the item does not have the attribute.
+    pub fn total_attribute(&self, name: &str) -> f64 {
+        self.attributes
+            .get(name)
+            .map(|attr| attr.initial_value)
+            .unwrap_or(0.0)
+    }
+
+    /// Compare two items by the total value of an attribute
+    pub fn compare_by_attribute(&self, other: &Item, name: &str) -> Ordering {
+        self.total_attribute(name).total_cmp(&other.total_attribute(name))
+    }
+
+    /// Replace the recorded contributions for an attribute with a single source
+    pub fn set_attribute_source(&mut self, name: &str, source: &str, amount: f64) {
+        self.attribute_sources
+            .insert(name.to_string(), vec![(source.to_string(), amount)]);
+    }
+
+    /// Record an additional contribution to an attribute
+    pub fn add_attribute_source(&mut self, name: &str, source: &str, amount: f64) {
+        self.attribute_sources
+            .entry(name.to_string())
+            .or_default()
+            .push((source.to_string(), amount));
+    }
+
+    /// Get the `(source, amount)` contributions that make up an attribute's value.
+    ///
+    /// Useful for tooltips such as "Damage: 20 (15 base + 5 Flaming)".
+    pub fn get_attribute_breakdown(&self, name: &str) -> Option<&[(String, f64)]> {
+        self.attribute_sources.get(name).map(|sources| sources.as_slice())
+    }
+
     pub fn set_metadata(&mut self, key: &str, value: serde_json::Value) {
         self.metadata.insert(key.to_string(), value);
     }
@@ -498,6 +909,327 @@ impl Item {
     pub fn has_metadata(&self, key: &str) -> bool {
         self.metadata.contains_key(key)
     }
+
+    /// Full name with the prefix and suffix applied, e.g. "Flaming Longsword of Fire"
+    pub fn display_name(&self) -> String {
+        [self.prefix.get_name(), self.name.as_str(), self.suffix.get_name()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Renders an item on one line for logging, e.g.
+/// `Flaming Longsword of Fire [rare weapon/sword] lvl 12 {damage: 18}`.
+///
+/// The level comes from the item's [`level_attribute`](Item::get_level_attribute) and is left
+/// out if the item has none; the remaining attributes are listed by name using
+/// [`ItemAttribute::format_value`].
+impl std::fmt::Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} [{} {}/{}]",
+            self.display_name(),
+            self.quality,
+            self.item_type,
+            self.subtype
+        )?;
+        if let Some(level) = self.attributes.get(&self.level_attribute) {
+            write!(f, " lvl {}", level.format_value())?;
+        }
+
+        let attributes: Vec<String> = self
+            .get_attributes_ordered()
+            .into_iter()
+            .filter(|(name, _)| *name != self.level_attribute)
+            .map(|(name, attr)| format!("{}: {}", name, attr.format_value()))
+            .collect();
+        write!(f, " {{{}}}", attributes.join(", "))
+    }
+}
+
+/// Sorts items by the total value of an attribute, highest first.
+///
+/// Items without the attribute count as 0.0. Useful for presenting the "best" item first.
+pub fn sort_items_by_attribute(items: &mut [Item], name: &str) {
+    items.sort_by(|a, b| b.compare_by_attribute(a, name));
+}
+
+/// Serializes items to JSON with camelCase field names, for clients that expect them.
+///
+/// Field names of items, affixes and attributes are converted (`initial_value` becomes
+/// `initialValue`) and `type` becomes `itemType`. Attribute names and metadata keys are data, not
+/// fields, so they are left as configured.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let json = praeda::to_json_camel(&items)?;
+/// // [{"name":"longsword","quality":"common","itemType":"weapon",...}]
+/// ```
+pub fn to_json_camel(items: &[Item]) -> Result<String> {
+    let mut value = serde_json::to_value(items)?;
+    if let serde_json::Value::Array(items) = &mut value {
+        for item in items.iter_mut() {
+            camel_case_keys(item);
+            let Some(item) = item.as_object_mut() else { continue };
+            if let Some(item_type) = item.remove("type") {
+                item.insert("itemType".to_string(), item_type);
+            }
+            for affix in ["prefix", "suffix"] {
+                if let Some(affix) = item.get_mut(affix) {
+                    camel_case_keys(affix);
+                    if let Some(serde_json::Value::Array(attributes)) = affix.get_mut("attributes") {
+                        attributes.iter_mut().for_each(camel_case_keys);
+                    }
+                }
+            }
+            if let Some(serde_json::Value::Object(attributes)) = item.get_mut("attributes") {
+                attributes.values_mut().for_each(camel_case_keys);
+            }
+        }
+    }
+    Ok(serde_json::to_string(&value)?)
+}
+
+/// Item view with attributes grouped by origin, for clients that need to tell base stats from
+/// affix bonuses. Built from an item's attribute breakdown (see
+/// [`Item::get_attribute_breakdown`]).
+///
+/// # Fields
+///
+/// * `attributes` - Final value of each attribute, as on the item
+/// * `base_attributes` - Base contribution of each attribute. Attributes without a recorded
+///   breakdown (e.g. on hand-built items) are listed here with their final value
+/// * `affix_attributes` - Per affix name, the amount it contributed to each attribute
+/// * `clamp_adjustments` - Per attribute, the total change made by clamping to bounds or to
+///   the attribute floor. Attributes that were never clamped are left out
+///
+/// For generated items, each final value is the sum of its base value, its affix
+/// contributions and its clamp adjustment.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GroupedItem {
+    pub name: String,
+    pub quality: String,
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub subtype: String,
+    pub prefix: String,
+    pub suffix: String,
+    pub attributes: HashMap<String, f64>,
+    pub base_attributes: HashMap<String, f64>,
+    pub affix_attributes: HashMap<String, HashMap<String, f64>>,
+    pub clamp_adjustments: HashMap<String, f64>,
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+impl From<&Item> for GroupedItem {
+    fn from(item: &Item) -> Self {
+        let mut base_attributes = HashMap::new();
+        let mut affix_attributes: HashMap<String, HashMap<String, f64>> = HashMap::new();
+        let mut clamp_adjustments = HashMap::new();
+        for (name, attr) in &item.attributes {
+            let Some(sources) = item.get_attribute_breakdown(name) else {
+                base_attributes.insert(name.clone(), attr.initial_value);
+                continue;
+            };
+            for (source, amount) in sources {
+                let group = match source.as_str() {
+                    crate::generator::BASE_ATTRIBUTE_SOURCE => &mut base_attributes,
+                    crate::generator::CLAMP_ATTRIBUTE_SOURCE => &mut clamp_adjustments,
+                    _ => affix_attributes.entry(source.clone()).or_default(),
+                };
+                *group.entry(name.clone()).or_insert(0.0) += amount;
+            }
+        }
+
+        GroupedItem {
+            name: item.name.clone(),
+            quality: item.quality.clone(),
+            item_type: item.item_type.clone(),
+            subtype: item.subtype.clone(),
+            prefix: item.prefix.name.clone(),
+            suffix: item.suffix.name.clone(),
+            attributes: item
+                .attributes
+                .iter()
+                .map(|(name, attr)| (name.clone(), attr.initial_value))
+                .collect(),
+            base_attributes,
+            affix_attributes,
+            clamp_adjustments,
+            metadata: item.metadata.clone(),
+        }
+    }
+}
+
+/// Serializes items to JSON as [`GroupedItem`]s, separating base attributes from affix
+/// attributes.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let json = praeda::to_json_grouped(&items)?;
+/// // [{"name":"Longsword",...,"base_attributes":{"damage":15.0},
+/// //   "affix_attributes":{"Sharp":{"damage":5.0}},...}]
+/// ```
+pub fn to_json_grouped(items: &[Item]) -> Result<String> {
+    let grouped: Vec<GroupedItem> = items.iter().map(GroupedItem::from).collect();
+    Ok(serde_json::to_string(&grouped)?)
+}
+
+/// Converts the keys of a JSON object (not its nested objects) from snake_case to camelCase
+fn camel_case_keys(value: &mut serde_json::Value) {
+    if let serde_json::Value::Object(map) = value {
+        let fields = std::mem::take(map);
+        for (key, field) in fields {
+            let mut camel = String::with_capacity(key.len());
+            let mut upper = false;
+            for c in key.chars() {
+                if c == '_' {
+                    upper = true;
+                } else if upper {
+                    camel.extend(c.to_uppercase());
+                    upper = false;
+                } else {
+                    camel.push(c);
+                }
+            }
+            map.insert(camel, field);
+        }
+    }
+}
+
+/// Closure building a fallback name from an item type and subtype
+pub type NameFallbackFn = Arc<dyn Fn(&str, &str) -> String + Send + Sync>;
+
+/// Strategy for naming items whose type/subtype has no configured names.
+///
+/// # Variants
+///
+/// * `Subtype` - Use the subtype verbatim (e.g., "sword"). This is the default.
+/// * `SubtypeWithType` - Use `"{subtype} ({type})"` (e.g., "sword (weapon)")
+/// * `Custom` - Call a closure with the item type and subtype
+///
+/// # Example
+///
+/// ```rust,ignore
+/// generator.set_name_fallback(NameFallback::Custom(Arc::new(|item_type, subtype| {
+///     format!("Unnamed {} {}", subtype, item_type)
+/// })));
+/// ```
+#[derive(Clone, Default)]
+pub enum NameFallback {
+    #[default]
+    Subtype,
+    SubtypeWithType,
+    Custom(NameFallbackFn),
+}
+
+impl NameFallback {
+    /// Builds the fallback name for an item type and subtype
+    pub fn name_for(&self, item_type: &str, subtype: &str) -> String {
+        match self {
+            NameFallback::Subtype => subtype.to_string(),
+            NameFallback::SubtypeWithType => format!("{} ({})", subtype, item_type),
+            NameFallback::Custom(fallback) => fallback(item_type, subtype),
+        }
+    }
+}
+
+impl std::fmt::Debug for NameFallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameFallback::Subtype => write!(f, "Subtype"),
+            NameFallback::SubtypeWithType => write!(f, "SubtypeWithType"),
+            NameFallback::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Display hint for an attribute value, used when rendering tooltips.
+///
+/// The discriminants are stable and are what the FFI exposes on `CItemAttribute::format`.
+///
+/// # Variants
+///
+/// * `Flat` - A plain number, e.g. `15`
+/// * `Percent` - A percentage, e.g. `15%`
+/// * `PerSecond` - A rate, e.g. `15/s`
+/// * `Duration` - A duration in seconds, e.g. `15s`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AttributeFormat {
+    #[default]
+    Flat = 0,
+    Percent = 1,
+    PerSecond = 2,
+    Duration = 3,
+}
+
+/// How an attribute combines with an attribute of the same name already on an item.
+///
+/// Affix attributes are applied in two passes: every `Add` from the prefix and suffix first,
+/// then every `Multiply` against the summed value, as in many games.
+///
+/// # Variants
+///
+/// * `Add` - `initial_value` is added to the existing value
+/// * `Multiply` - The existing value is multiplied by `initial_value`, so `1.2` is +20%. Has no
+///   effect if the item does not have the attribute yet
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ApplyMode {
+    #[default]
+    Add,
+    Multiply,
+}
+
+/// How attribute values grow with item level.
+///
+/// # Variants
+///
+/// * `Linear` - Adds `level * scaling_factor`
+/// * `Exponential` - Multiplies by `scaling_factor^level`
+/// * `Logarithmic` - Adds `scaling_factor * ln(1 + level)`, so each level adds less than the last
+/// * `Sigmoid` - Adds up to `scaling_factor` along a logistic curve centered on `midpoint`, with
+///   `steepness` controlling how sharply it rises
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ScalingCurve {
+    Linear,
+    Exponential,
+    Logarithmic,
+    Sigmoid { midpoint: f64, steepness: f64 },
+}
+
+impl ScalingCurve {
+    /// Maps the legacy `linear` flag to a curve
+    pub fn from_linear(linear: bool) -> Self {
+        if linear {
+            ScalingCurve::Linear
+        } else {
+            ScalingCurve::Exponential
+        }
+    }
+}
+
+/// Order applied to a generated batch through [`GeneratorOptions::sort`].
+///
+/// Every key sorts from best to worst, and items that compare equal are ordered by name.
+///
+/// # Variants
+///
+/// * `QualityRank` - Rarest quality first, by
+///   [`get_quality_rank`](crate::generator::PraedaGenerator::get_quality_rank). Qualities without
+///   a rank come last
+/// * `Level` - Highest item level first, read from `level_attribute_name`
+/// * `Attribute` - Highest value of the named attribute first. Items without it come last
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SortKey {
+    QualityRank,
+    Level,
+    Attribute(String),
 }
 
 /// Options controlling loot generation behavior.
@@ -511,10 +1243,45 @@ impl Item {
 /// * `base_level` - Starting level for items (used for attribute scaling)
 /// * `level_variance` - Range around base_level (actual level = base ± variance)
 /// * `affix_chance` - Probability (0.0-1.0) that an affix is selected for each item
-/// * `linear` - If true, attributes scale linearly; if false, exponentially
+/// * `linear` - If true, attributes scale linearly; if false, exponentially. Ignored when `curve` is set
 /// * `scaling_factor` - Multiplier applied per level
 ///   - Linear: adds `level * scaling_factor` to attribute value
 ///   - Exponential: multiplies attribute value by `scaling_factor^level`
+/// * `affix_chance_per_level` - Added to `affix_chance` per generated item level, for both
+///   affixes and optional attributes; the resulting chance is clamped to 0.0-1.0 (default 0.0)
+/// * `level_attribute_name` - Name of the attribute that stores the generated item level
+///   (default `"level"`)
+/// * `luck` - Boosts rarer qualities during the quality roll. The rarest quality's weight is
+///   multiplied by `1 + luck`, the most common is unchanged, and tiers in between are scaled
+///   proportionally by rank (default 0.0)
+/// * `attribute_floor` - Lowest value an optional or affix attribute may end up at after
+///   merging, raised to the attribute's `min` when it has bounds. `None` allows negative totals
+///   (default `Some(0.0)`)
+/// * `seed` - If set, generation is reproducible: the same seed and configuration always yield
+///   the same items, regardless of the order the configuration was inserted in (default `None`)
+/// * `curve` - [`ScalingCurve`] used for attribute scaling. When `None`, `linear` picks between
+///   linear and exponential (default `None`)
+/// * `strict_affix_matching` - If true, only affixes registered for the item's type (`(type, "")`)
+///   or exact type/subtype are rolled; global and bare-subtype affixes are skipped, so a subtype
+///   name shared by several types does not leak affixes across them (default `false`)
+/// * `locale` - If set, item names are drawn from names registered for this locale with
+///   `set_item_localized`, falling back to the default names (default `None`)
+/// * `max_level` - If set, generated item levels are capped at this value after the random roll,
+///   e.g. the player's level plus a margin (default `None`)
+/// * `unique_names_within_batch` - If true, names for each type/subtype are drawn without
+///   replacement within a single call, so a name only repeats once the pool is exhausted
+///   (default `false`)
+/// * `level_weighted_affixes` - If true, affixes whose `min_level` is close to the generated item
+///   level are favored: each valid affix is weighted by `1 / (1 + |level - min_level|)` instead of
+///   all being equally likely (default `false`)
+/// * `affix_budget` - If set, caps the total number of affixes per item regardless of slot.
+///   With a budget of 1, an item that rolled both a prefix and a suffix keeps one of them at
+///   random. The budget also caps a quality's minimum affix count (default `None`)
+/// * `power_budget` - If set, caps the total [`Affix::get_power_cost`] of the affixes on an
+///   item. Affixes costing more than what remains are skipped when picking, prefix first
+///   (default `None`)
+/// * `sort` - If set, the generated batch is sorted by this key before it is returned or
+///   stored (default `None`)
 ///
 /// # Example
 ///
@@ -526,6 +1293,7 @@ impl Item {
 ///     affix_chance: 0.25,        // 25% chance for affixes
 ///     linear: true,              // Linear attribute scaling
 ///     scaling_factor: 1.5,       // Adds 1.5 per level linearly
+///     ..Default::default()
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -536,6 +1304,42 @@ pub struct GeneratorOptions {
     pub affix_chance: f64,
     pub linear: bool,
     pub scaling_factor: f64,
+    #[serde(default)]
+    pub affix_chance_per_level: f64,
+    #[serde(default = "default_level_attribute_name")]
+    pub level_attribute_name: String,
+    #[serde(default)]
+    pub luck: f64,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default = "default_attribute_floor")]
+    pub attribute_floor: Option<f64>,
+    #[serde(default)]
+    pub curve: Option<ScalingCurve>,
+    #[serde(default)]
+    pub strict_affix_matching: bool,
+    #[serde(default)]
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub max_level: Option<f64>,
+    #[serde(default)]
+    pub unique_names_within_batch: bool,
+    #[serde(default)]
+    pub level_weighted_affixes: bool,
+    #[serde(default)]
+    pub affix_budget: Option<u32>,
+    #[serde(default)]
+    pub power_budget: Option<f64>,
+    #[serde(default)]
+    pub sort: Option<SortKey>,
+}
+
+fn default_level_attribute_name() -> String {
+    "level".to_string()
+}
+
+fn default_attribute_floor() -> Option<f64> {
+    Some(0.0)
 }
 
 impl GeneratorOptions {
@@ -554,15 +1358,97 @@ impl GeneratorOptions {
             affix_chance,
             linear,
             scaling_factor,
+            affix_chance_per_level: 0.0,
+            level_attribute_name: default_level_attribute_name(),
+            luck: 0.0,
+            seed: None,
+            attribute_floor: default_attribute_floor(),
+            curve: None,
+            strict_affix_matching: false,
+            locale: None,
+            max_level: None,
+            unique_names_within_batch: false,
+            level_weighted_affixes: false,
+            affix_budget: None,
+            power_budget: None,
+            sort: None,
         }
     }
 
     pub fn is_linear(&self) -> bool {
-        self.linear
+        self.scaling_curve() == ScalingCurve::Linear
     }
 
     pub fn is_exponential(&self) -> bool {
-        !self.linear
+        self.scaling_curve() == ScalingCurve::Exponential
+    }
+
+    /// The curve used for attribute scaling, falling back to `linear` when `curve` is unset
+    pub fn scaling_curve(&self) -> ScalingCurve {
+        self.curve.unwrap_or(ScalingCurve::from_linear(self.linear))
+    }
+
+    /// Checks that the options describe a sensible generation run.
+    ///
+    /// Rejects a non-finite numeric field, a negative `base_level`, `level_variance` or
+    /// `scaling_factor`, and an `affix_chance` outside 0.0-1.0. Asking for zero items is allowed
+    /// and yields an empty batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PraedaError::InvalidData`] naming the first invalid field.
+    pub fn validate(&self) -> Result<()> {
+        let fields = [
+            ("base_level", Some(self.base_level)),
+            ("level_variance", Some(self.level_variance)),
+            ("affix_chance", Some(self.affix_chance)),
+            ("scaling_factor", Some(self.scaling_factor)),
+            ("affix_chance_per_level", Some(self.affix_chance_per_level)),
+            ("luck", Some(self.luck)),
+            ("attribute_floor", self.attribute_floor),
+            ("max_level", self.max_level),
+            ("power_budget", self.power_budget),
+        ];
+        for (name, value) in fields {
+            if let Some(value) = value
+                && !value.is_finite()
+            {
+                return Err(PraedaError::InvalidData(format!(
+                    "{} must be finite, got {}",
+                    name, value
+                )));
+            }
+        }
+        if self.base_level < 0.0 {
+            return Err(PraedaError::InvalidData(format!(
+                "base_level must not be negative, got {}",
+                self.base_level
+            )));
+        }
+        if self.level_variance < 0.0 {
+            return Err(PraedaError::InvalidData(format!(
+                "level_variance must not be negative, got {}",
+                self.level_variance
+            )));
+        }
+        if self.scaling_factor < 0.0 {
+            return Err(PraedaError::InvalidData(format!(
+                "scaling_factor must not be negative, got {}",
+                self.scaling_factor
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.affix_chance) {
+            return Err(PraedaError::InvalidData(format!(
+                "affix_chance must be between 0.0 and 1.0, got {}",
+                self.affix_chance
+            )));
+        }
+        Ok(())
+    }
+
+    /// Effective affix chance for an item of the given level, clamped to 0.0-1.0
+    pub fn affix_chance_at_level(&self, level: f64) -> f64 {
+        (self.affix_chance + self.affix_chance_per_level * level).clamp(0.0, 1.0)
     }
 }
 
@@ -575,6 +1461,20 @@ impl Default for GeneratorOptions {
             affix_chance: 0.25,
             linear: true,
             scaling_factor: 1.0,
+            affix_chance_per_level: 0.0,
+            level_attribute_name: default_level_attribute_name(),
+            luck: 0.0,
+            seed: None,
+            attribute_floor: default_attribute_floor(),
+            curve: None,
+            strict_affix_matching: false,
+            locale: None,
+            max_level: None,
+            unique_names_within_batch: false,
+            level_weighted_affixes: false,
+            affix_budget: None,
+            power_budget: None,
+            sort: None,
         }
     }
 }
@@ -589,6 +1489,8 @@ impl Default for GeneratorOptions {
 /// * `quality_override` - If set, forces items to this quality; if empty, quality is random
 /// * `type_override` - If set, forces items to this type; if empty, type is random
 /// * `subtype_override` - If set, forces items to this subtype; if empty, subtype is random
+/// * `quality_whitelist` - If not empty, the quality roll only picks from these qualities, with
+///   their weights renormalized among themselves. Ignored when `quality_override` is set
 ///
 /// # Example
 ///
@@ -598,6 +1500,13 @@ impl Default for GeneratorOptions {
 ///     quality_override: "legendary".to_string(),
 ///     type_override: "weapon".to_string(),
 ///     subtype_override: "".to_string(),  // Random subtype
+///     quality_whitelist: Vec::new(),
+/// };
+///
+/// // A "blue-or-better" vendor
+/// let overrides = GeneratorOverrides {
+///     quality_whitelist: vec!["rare".to_string(), "epic".to_string()],
+///     ..GeneratorOverrides::empty()
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -605,6 +1514,8 @@ pub struct GeneratorOverrides {
     pub quality_override: String,
     pub type_override: String,
     pub subtype_override: String,
+    #[serde(default)]
+    pub quality_whitelist: Vec<String>,
 }
 
 impl GeneratorOverrides {
@@ -617,6 +1528,7 @@ impl GeneratorOverrides {
             quality_override: quality_override.to_string(),
             type_override: type_override.to_string(),
             subtype_override: subtype_override.to_string(),
+            quality_whitelist: Vec::new(),
         }
     }
 
@@ -625,6 +1537,7 @@ impl GeneratorOverrides {
             quality_override: String::new(),
             type_override: String::new(),
             subtype_override: String::new(),
+            quality_whitelist: Vec::new(),
         }
     }
 
@@ -639,6 +1552,72 @@ impl GeneratorOverrides {
     pub fn get_subtype_override(&self) -> &str {
         &self.subtype_override
     }
+
+    pub fn get_quality_whitelist(&self) -> &[String] {
+        &self.quality_whitelist
+    }
+}
+
+/// Counters collected by [`PraedaGenerator::generate_loot_reported`](crate::PraedaGenerator::generate_loot_reported).
+///
+/// # Fields
+///
+/// * `items_generated` - Number of items produced by the run
+/// * `rng_calls` - Number of times the random number generator was drawn from
+/// * `affixes_applied` - Number of prefixes and suffixes applied across all items
+/// * `unique_name_retries` - With `unique_names_within_batch`, how many times a name pool ran
+///   out and was reshuffled, so names started repeating. Always 0 otherwise
+/// * `seed` - Seed the run used; drawn from entropy when `options.seed` was `None`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GenerationReport {
+    pub items_generated: usize,
+    pub rng_calls: u64,
+    pub affixes_applied: u64,
+    pub unique_name_retries: u64,
+    #[serde(default)]
+    pub seed: u64,
+}
+
+/// Aggregated results of [`PraedaGenerator::simulate`](crate::PraedaGenerator::simulate).
+///
+/// # Fields
+///
+/// * `trials` - Number of drops rolled
+/// * `items_generated` - Number of drops that produced an item (empty drops excluded)
+/// * `quality_counts` - Items per quality
+/// * `type_counts` - Items per item type
+/// * `subtype_counts` - Items per subtype
+/// * `affix_count` - Prefixes and suffixes applied across all items
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LootStats {
+    pub trials: usize,
+    pub items_generated: usize,
+    pub quality_counts: HashMap<String, usize>,
+    pub type_counts: HashMap<String, usize>,
+    pub subtype_counts: HashMap<String, usize>,
+    pub affix_count: u64,
+}
+
+impl LootStats {
+    /// Add a generated item to the totals
+    pub fn record(&mut self, item: &Item) {
+        self.items_generated += 1;
+        *self.quality_counts.entry(item.get_quality().to_string()).or_insert(0) += 1;
+        *self.type_counts.entry(item.get_type().to_string()).or_insert(0) += 1;
+        *self.subtype_counts.entry(item.get_subtype().to_string()).or_insert(0) += 1;
+        self.affix_count += [item.get_prefix(), item.get_suffix()]
+            .iter()
+            .filter(|affix| !affix.get_name().is_empty())
+            .count() as u64;
+    }
+
+    /// Fraction of trials that dropped an item of the given quality
+    pub fn quality_rate(&self, quality: &str) -> f64 {
+        if self.trials == 0 {
+            return 0.0;
+        }
+        self.quality_counts.get(quality).copied().unwrap_or(0) as f64 / self.trials as f64
+    }
 }
 
 // ============================================================================
@@ -652,15 +1631,120 @@ pub struct TomlConfig {
     #[serde(default)]
     pub item_types: Vec<ItemType>,
     #[serde(default)]
-    pub item_attributes: Vec<TomlItemAttributes>,
+    pub item_attributes: TomlAttributeList,
     #[serde(default)]
     pub item_list: Vec<TomlItemList>,
     #[serde(default)]
     pub item_affixes: Vec<TomlItemAffixes>,
 }
 
+/// The `item_attributes` section, in either of two forms.
+///
+/// The nested form is an array of tables, one per type/subtype:
+///
+/// ```toml
+/// [[item_attributes]]
+/// item_type = "weapon"
+/// subtype = "sword"
+/// [[item_attributes.attributes]]
+/// name = "damage"
+/// initial_value = 10.0
+/// min = 1.0
+/// max = 20.0
+/// required = true
+/// ```
+///
+/// The flat form is a table keyed by type, then subtype (`""` for type-wide), holding inline
+/// arrays of attributes:
+///
+/// ```toml
+/// [item_attributes]
+/// weapon.sword = [
+///     { name = "damage", initial_value = 10.0, min = 1.0, max = 20.0, required = true },
+/// ]
+/// ```
+///
+/// An array is always read as the nested form and a table as the flat form, so a mistake in
+/// either reports the field that is wrong.
+#[derive(Debug)]
+pub enum TomlAttributeList {
+    Nested(Vec<TomlItemAttributes>),
+    Flat(HashMap<String, HashMap<String, Vec<ItemAttribute>>>),
+}
+
+impl<'de> Deserialize<'de> for TomlAttributeList {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct AttributeListVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for AttributeListVisitor {
+            type Value = TomlAttributeList;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an array of item attribute tables or a table keyed by type and subtype")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, seq: A) -> std::result::Result<Self::Value, A::Error> {
+                Deserialize::deserialize(serde::de::value::SeqAccessDeserializer::new(seq))
+                    .map(TomlAttributeList::Nested)
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, map: A) -> std::result::Result<Self::Value, A::Error> {
+                Deserialize::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+                    .map(TomlAttributeList::Flat)
+            }
+        }
+
+        deserializer.deserialize_any(AttributeListVisitor)
+    }
+}
+
+impl Default for TomlAttributeList {
+    fn default() -> Self {
+        TomlAttributeList::Nested(Vec::new())
+    }
+}
+
+impl TomlAttributeList {
+    /// Converts either form into one entry per type/subtype
+    pub fn into_entries(self) -> Vec<TomlItemAttributes> {
+        match self {
+            TomlAttributeList::Nested(entries) => entries,
+            TomlAttributeList::Flat(types) => types
+                .into_iter()
+                .flat_map(|(item_type, subtypes)| {
+                    subtypes.into_iter().map(move |(subtype, attributes)| TomlItemAttributes {
+                        item_type: item_type.clone(),
+                        subtype,
+                        attributes,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Read-only view of a generator's configuration, returned by
+/// [`PraedaGenerator::get_config_snapshot`].
+///
+/// The fields mirror [`TomlConfig`], so the snapshot serializes to the same shape
+/// [`PraedaGenerator::load_data`] reads, plus the generator-only `quality_data_f64` and
+/// `quality_metadata`. Lists are sorted by type and subtype so the output is stable.
+///
+/// [`PraedaGenerator::get_config_snapshot`]: crate::PraedaGenerator::get_config_snapshot
+/// [`PraedaGenerator::load_data`]: crate::PraedaGenerator::load_data
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ConfigSnapshot {
+    pub quality_data: HashMap<String, i32>,
+    pub quality_data_f64: HashMap<String, f64>,
+    pub quality_metadata: HashMap<String, HashMap<String, serde_json::Value>>,
+    pub item_types: Vec<ItemType>,
+    pub item_attributes: Vec<TomlItemAttributes>,
+    pub item_list: Vec<TomlItemList>,
+    pub item_affixes: Vec<TomlItemAffixes>,
+}
+
 /// Item attributes for a specific type/subtype combination
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TomlItemAttributes {
     #[serde(default)]
     pub item_type: String,
@@ -671,7 +1755,10 @@ pub struct TomlItemAttributes {
 }
 
 /// Item list for a specific type/subtype combination
-#[derive(Debug, Deserialize)]
+///
+/// `prefixes` and `suffixes` optionally define affixes inline, keeping a subtype's names and
+/// affixes together. They are added to any affixes from `[[item_affixes]]` for the same key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TomlItemList {
     pub item_type: String,
     pub subtype: String,
@@ -679,10 +1766,14 @@ pub struct TomlItemList {
     pub names: Vec<String>,
     #[serde(default)]
     pub item_metadata: HashMap<String, HashMap<String, serde_json::Value>>,
+    #[serde(default)]
+    pub prefixes: Vec<Affix>,
+    #[serde(default)]
+    pub suffixes: Vec<Affix>,
 }
 
 /// Item affixes for a specific type/subtype combination
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TomlItemAffixes {
     #[serde(default)]
     pub item_type: String,
@@ -692,6 +1783,7 @@ pub struct TomlItemAffixes {
     pub prefixes: Vec<Affix>,
     #[serde(default)]
     pub suffixes: Vec<Affix>,
+    /// Subtype metadata. Nested TOML tables and arrays are kept as JSON objects and arrays.
     #[serde(default)]
     pub metadata: HashMap<String, serde_json::Value>,
 }
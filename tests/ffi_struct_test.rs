@@ -19,6 +19,27 @@ fn c_str_to_string(ptr: *const c_char) -> String {
     }
 }
 
+// Helper function to create a generator with a single common weapon/sword to generate
+fn configured_generator() -> *mut PraedaGeneratorHandle {
+    unsafe {
+        let handle = praeda_generator_new();
+        assert!(!handle.is_null());
+        let _ = praeda_generator_set_quality_data(
+            handle,
+            CString::new("common").unwrap().as_ptr(),
+            100,
+        );
+        let _ = praeda_generator_set_item_type(handle, CString::new("weapon").unwrap().as_ptr(), 1);
+        let _ = praeda_generator_set_item_subtype(
+            handle,
+            CString::new("weapon").unwrap().as_ptr(),
+            CString::new("sword").unwrap().as_ptr(),
+            1,
+        );
+        handle
+    }
+}
+
 // ============================================================================
 // Basic Generator Lifecycle Tests
 // ============================================================================
@@ -157,6 +178,50 @@ fn test_set_attribute() {
     }
 }
 
+#[test]
+fn test_set_attribute_ex() {
+    unsafe {
+        let handle = configured_generator();
+
+        let result = praeda_generator_set_attribute_ex(
+            handle,
+            CString::new("weapon").unwrap().as_ptr(),
+            CString::new("").unwrap().as_ptr(),
+            CString::new("damage").unwrap().as_ptr(),
+            15.0,
+            5.0,
+            30.0,
+            1,   // required = true
+            0.4, // chance
+            2.5, // scaling_factor
+        );
+        assert_eq!(result, 0, "Setting attribute should succeed");
+
+        let mut buffer: Vec<std::mem::MaybeUninit<CItem>> = Vec::with_capacity(1);
+        buffer.resize_with(1, std::mem::MaybeUninit::uninit);
+        let buf_ptr = buffer.as_mut_ptr() as *mut CItem;
+        let mut written: u32 = 0;
+        let mut error_ptr = std::ptr::null_mut();
+        let result = praeda_generator_generate_into(
+            handle, buf_ptr, 1, 1, 5.0, 0.0, 0.0, 1, 1.0, &mut written, &mut error_ptr,
+        );
+        assert_eq!(result, 0);
+        assert_eq!(written, 1);
+
+        let item = &mut *buf_ptr;
+        let attributes = std::slice::from_raw_parts(item.attributes, item.attributes_count as usize);
+        let damage = attributes
+            .iter()
+            .find(|attr| c_str_to_string(attr.name) == "damage")
+            .expect("damage attribute should be generated");
+        assert_eq!(damage.chance, 0.4);
+        assert_eq!(damage.scaling_factor, 2.5);
+
+        praeda_item_free(item);
+        praeda_generator_free(handle);
+    }
+}
+
 #[test]
 fn test_set_item_names() {
     unsafe {
@@ -555,9 +620,10 @@ fn test_load_toml_invalid_syntax() {
         );
 
         assert_eq!(
-            result, -1,
+            result, PRAEDA_ERROR_INVALID_TOML,
             "Loading invalid TOML should fail"
         );
+        assert_eq!(praeda_last_error_code(), PRAEDA_ERROR_INVALID_TOML);
 
         // Error message should be available
         if !error_ptr.is_null() {
@@ -730,10 +796,38 @@ fn test_item_array_bounds() {
             assert!(!item_ptr.is_null());
         }
 
-        // Accessing beyond bounds should still work but might return invalid data
-        // This is expected C behavior - callers are responsible for bounds checking
-        let _out_of_bounds = praeda_item_array_get(array_handle, count + 10);
-        // We don't assert about this - C FFI doesn't guarantee safety at boundaries
+        // Accessing beyond bounds returns null
+        assert!(praeda_item_array_get(array_handle, count + 10).is_null());
+
+        praeda_item_array_free(array_handle);
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_item_array_get_out_of_bounds_returns_null() {
+    unsafe {
+        let handle = praeda_generator_new();
+        let _ = praeda_generator_set_quality_data(handle, CString::new("common").unwrap().as_ptr(), 100);
+        let _ = praeda_generator_set_item_type(handle, CString::new("weapon").unwrap().as_ptr(), 1);
+        let _ = praeda_generator_set_item_subtype(
+            handle,
+            CString::new("weapon").unwrap().as_ptr(),
+            CString::new("sword").unwrap().as_ptr(),
+            1,
+        );
+
+        let mut error_ptr = std::ptr::null_mut();
+        let array_handle = praeda_generator_generate_loot(
+            handle, 3, 10.0, 2.0, 0.25, 1, 1.0, &mut error_ptr,
+        );
+        let count = praeda_item_array_count(array_handle);
+        assert_eq!(count, 3);
+
+        assert!(!praeda_item_array_get(array_handle, count - 1).is_null());
+        assert!(praeda_item_array_get(array_handle, count).is_null(), "index == count must be null");
+        assert!(praeda_item_array_get(array_handle, count + 100).is_null(), "index past count must be null");
+        assert!(praeda_item_array_get(std::ptr::null(), 0).is_null());
 
         praeda_item_array_free(array_handle);
         praeda_generator_free(handle);
@@ -971,3 +1065,426 @@ fn test_set_attribute_null_handle() {
         assert_eq!(result, -1, "Setting attribute on null handle should fail");
     }
 }
+
+#[test]
+fn test_item_has_prefix_and_suffix() {
+    unsafe {
+        let handle = praeda_generator_new();
+        assert!(!handle.is_null());
+
+        let _ = praeda_generator_set_quality_data(handle, CString::new("common").unwrap().as_ptr(), 100);
+        let _ = praeda_generator_set_item_type(handle, CString::new("weapon").unwrap().as_ptr(), 1);
+        let _ = praeda_generator_set_item_subtype(
+            handle,
+            CString::new("weapon").unwrap().as_ptr(),
+            CString::new("sword").unwrap().as_ptr(),
+            1,
+        );
+
+        // Prefix only, so suffixes are never present
+        let _ = praeda_generator_set_prefix_attribute(
+            handle,
+            CString::new("weapon").unwrap().as_ptr(),
+            CString::new("sword").unwrap().as_ptr(),
+            CString::new("sharp").unwrap().as_ptr(),
+            CString::new("damage").unwrap().as_ptr(),
+            5.0,
+            0.0,
+            15.0,
+            1,
+        );
+
+        let mut error_ptr = std::ptr::null_mut();
+        let array_handle = praeda_generator_generate_loot(
+            handle,
+            10,    // number_of_items
+            20.0,  // base_level
+            5.0,   // level_variance
+            1.0,   // affix_chance (100% for testing)
+            1,     // linear
+            1.0,   // scaling_factor
+            &mut error_ptr,
+        );
+        assert!(!array_handle.is_null(), "Item array should not be null");
+
+        let mut prefixed = 0;
+        for i in 0..praeda_item_array_count(array_handle) {
+            let item = praeda_item_array_get(array_handle, i);
+            if praeda_item_has_prefix(item) == 1 {
+                prefixed += 1;
+            }
+            assert_eq!(praeda_item_has_suffix(item), 0, "No suffixes are configured");
+        }
+        assert!(prefixed > 0, "At least some items should report a prefix");
+
+        praeda_item_array_free(array_handle);
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_item_has_affix_null_item() {
+    unsafe {
+        assert_eq!(praeda_item_has_prefix(std::ptr::null()), -1);
+        assert_eq!(praeda_item_has_suffix(std::ptr::null()), -1);
+    }
+}
+
+#[test]
+fn test_item_get_attribute_by_name() {
+    unsafe {
+        let handle = praeda_generator_new();
+        assert!(!handle.is_null());
+
+        let _ = praeda_generator_set_quality_data(handle, CString::new("common").unwrap().as_ptr(), 100);
+        let _ = praeda_generator_set_item_type(handle, CString::new("weapon").unwrap().as_ptr(), 1);
+        let _ = praeda_generator_set_item_subtype(
+            handle,
+            CString::new("weapon").unwrap().as_ptr(),
+            CString::new("sword").unwrap().as_ptr(),
+            1,
+        );
+        let _ = praeda_generator_set_attribute(
+            handle,
+            CString::new("weapon").unwrap().as_ptr(),
+            CString::new("sword").unwrap().as_ptr(),
+            CString::new("damage").unwrap().as_ptr(),
+            10.0,
+            0.0,
+            100.0,
+            1,
+        );
+
+        let mut error_ptr = std::ptr::null_mut();
+        let array_handle = praeda_generator_generate_loot(handle, 1, 5.0, 0.0, 0.0, 1, 1.0, &mut error_ptr);
+        assert!(!array_handle.is_null(), "Item array should not be null");
+        let item = praeda_item_array_get(array_handle, 0);
+
+        let mut attr = std::mem::zeroed::<CItemAttribute>();
+        let damage = CString::new("damage").unwrap();
+        assert_eq!(praeda_item_get_attribute(item, damage.as_ptr(), &mut attr), 0);
+        assert_eq!(c_str_to_string(attr.name), "damage");
+        assert_eq!(attr.initial_value, 15.0);
+        assert_eq!(attr.max, 100.0);
+        assert_eq!(attr.required, 1);
+
+        let missing = CString::new("armor").unwrap();
+        assert_eq!(praeda_item_get_attribute(item, missing.as_ptr(), &mut attr), -1);
+        assert_eq!(praeda_item_get_attribute(std::ptr::null(), damage.as_ptr(), &mut attr), -1);
+        assert_eq!(praeda_item_get_attribute(item, std::ptr::null(), &mut attr), -1);
+
+        praeda_item_array_free(array_handle);
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_generate_into_fixed_buffer() {
+    unsafe {
+        let handle = configured_generator();
+
+        let mut buffer: Vec<std::mem::MaybeUninit<CItem>> = Vec::with_capacity(4);
+        buffer.resize_with(4, std::mem::MaybeUninit::uninit);
+        let buf_ptr = buffer.as_mut_ptr() as *mut CItem;
+
+        // Ask for more items than fit; only the capacity is filled
+        let mut written: u32 = 0;
+        let mut error_ptr = std::ptr::null_mut();
+        let result = praeda_generator_generate_into(
+            handle,
+            buf_ptr,
+            4,     // capacity
+            10,    // number_of_items
+            5.0,   // base_level
+            1.0,   // level_variance
+            0.0,   // affix_chance
+            1,     // linear
+            1.0,   // scaling_factor
+            &mut written,
+            &mut error_ptr,
+        );
+
+        assert_eq!(result, 0, "generate_into should succeed");
+        assert!(error_ptr.is_null());
+        assert_eq!(written, 4, "Should fill the buffer up to capacity");
+
+        for i in 0..written as usize {
+            let item = &mut *buf_ptr.add(i);
+            assert_eq!(c_str_to_string(item.item_type), "weapon");
+            assert_eq!(c_str_to_string(item.subtype), "sword");
+            praeda_item_free(item);
+            assert!(item.name.is_null(), "Freed item strings should be nulled");
+        }
+
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_generate_into_null_buffer() {
+    unsafe {
+        let handle = praeda_generator_new();
+        let mut written: u32 = 7;
+        let mut error_ptr = std::ptr::null_mut();
+        let result = praeda_generator_generate_into(
+            handle,
+            std::ptr::null_mut(),
+            4,
+            4,
+            1.0,
+            0.0,
+            0.0,
+            1,
+            1.0,
+            &mut written,
+            &mut error_ptr,
+        );
+
+        assert_eq!(result, -1);
+        assert_eq!(written, 0);
+        assert!(!error_ptr.is_null(), "Error message should be set");
+
+        praeda_error_free(error_ptr);
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_item_attributes_emitted_in_name_order() {
+    unsafe {
+        let handle = configured_generator();
+        for name in ["speed", "armor", "damage"] {
+            let _ = praeda_generator_set_attribute(
+                handle,
+                CString::new("weapon").unwrap().as_ptr(),
+                CString::new("").unwrap().as_ptr(),
+                CString::new(name).unwrap().as_ptr(),
+                1.0,
+                0.0,
+                10.0,
+                1,
+            );
+        }
+
+        let mut error_ptr = std::ptr::null_mut();
+        let array_handle = praeda_generator_generate_loot(handle, 1, 5.0, 0.0, 0.0, 1, 1.0, &mut error_ptr);
+        let item = &*praeda_item_array_get(array_handle, 0);
+        let attributes = std::slice::from_raw_parts(item.attributes, item.attributes_count as usize);
+        let names: Vec<String> = attributes.iter().map(|attr| c_str_to_string(attr.name)).collect();
+        assert_eq!(names, vec!["armor", "damage", "level", "speed"]);
+
+        praeda_item_array_free(array_handle);
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_error_codes_distinguish_toml_and_generation_failures() {
+    unsafe {
+        let handle = praeda_generator_new();
+        let mut error_ptr = std::ptr::null_mut();
+
+        let toml_result = praeda_generator_load_toml(
+            handle,
+            CString::new("not = [valid").unwrap().as_ptr(),
+            &mut error_ptr,
+        );
+        assert_eq!(toml_result, PRAEDA_ERROR_INVALID_TOML);
+        praeda_error_free(error_ptr);
+
+        // Nothing is configured, so quality selection fails during generation
+        let mut buf: Vec<CItem> = Vec::with_capacity(1);
+        let mut written: u32 = 0;
+        let mut error_ptr = std::ptr::null_mut();
+        let gen_result = praeda_generator_generate_into(
+            handle,
+            buf.as_mut_ptr(),
+            1,
+            1,
+            1.0,
+            0.0,
+            0.0,
+            1,
+            1.0,
+            &mut written,
+            &mut error_ptr,
+        );
+        assert_eq!(gen_result, PRAEDA_ERROR_GENERATION);
+        assert_eq!(praeda_last_error_code(), PRAEDA_ERROR_GENERATION);
+        assert_ne!(toml_result, gen_result);
+        praeda_error_free(error_ptr);
+
+        let toml = r#"
+[quality_data]
+common = 1
+"#;
+        let result = praeda_generator_load_toml(
+            handle,
+            CString::new(toml).unwrap().as_ptr(),
+            std::ptr::null_mut(),
+        );
+        assert_eq!(result, 0);
+        assert_eq!(praeda_last_error_code(), 0);
+
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_item_attribute_format_discriminant() {
+    unsafe {
+        let handle = praeda_generator_new();
+        let toml_str = r#"
+[quality_data]
+common = 1
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+
+[[item_attributes]]
+item_type = "weapon"
+subtype = ""
+[[item_attributes.attributes]]
+name = "attack_speed"
+initial_value = 1.0
+min = 0.0
+max = 100.0
+required = true
+format = "PerSecond"
+"#;
+        let result = praeda_generator_load_toml(
+            handle,
+            CString::new(toml_str).unwrap().as_ptr(),
+            std::ptr::null_mut(),
+        );
+        assert_eq!(result, 0);
+
+        let mut error_ptr = std::ptr::null_mut();
+        let array_handle = praeda_generator_generate_loot(handle, 1, 5.0, 0.0, 0.0, 1, 1.0, &mut error_ptr);
+        let item = &*praeda_item_array_get(array_handle, 0);
+        let attributes = std::slice::from_raw_parts(item.attributes, item.attributes_count as usize);
+        for attr in attributes {
+            let expected = match c_str_to_string(attr.name).as_str() {
+                "attack_speed" => praeda::AttributeFormat::PerSecond,
+                _ => praeda::AttributeFormat::Flat,
+            };
+            assert_eq!(attr.format, expected as u8);
+        }
+
+        praeda_item_array_free(array_handle);
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_item_free_single_item_is_null_safe() {
+    unsafe {
+        praeda_item_free(std::ptr::null_mut());
+
+        let handle = configured_generator();
+
+        let mut item = std::mem::MaybeUninit::<CItem>::uninit();
+        let mut written: u32 = 0;
+        let result = praeda_generator_generate_into(
+            handle,
+            item.as_mut_ptr(),
+            1,
+            1,
+            5.0,
+            0.0,
+            0.0,
+            1,
+            1.0,
+            &mut written,
+            std::ptr::null_mut(),
+        );
+        assert_eq!(result, 0);
+        assert_eq!(written, 1);
+
+        let item = item.assume_init_mut();
+        assert_eq!(c_str_to_string(item.item_type), "weapon");
+        praeda_item_free(item);
+        assert!(item.name.is_null());
+        assert!(item.attributes.is_null());
+        assert_eq!(item.attributes_count, 0);
+        // A second free finds nothing left to release
+        praeda_item_free(item);
+
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_generator_validate_reports_issues() {
+    unsafe {
+        let handle = praeda_generator_new();
+        let toml_str = r#"
+[quality_data]
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+"#;
+        let result = praeda_generator_load_toml(
+            handle,
+            CString::new(toml_str).unwrap().as_ptr(),
+            std::ptr::null_mut(),
+        );
+        assert_eq!(result, 0);
+
+        let mut json_ptr = std::ptr::null_mut();
+        let issue_count = praeda_generator_validate(handle, &mut json_ptr);
+        assert!(issue_count > 0);
+        assert!(!json_ptr.is_null());
+        let issues: Vec<String> = serde_json::from_str(&c_str_to_string(json_ptr)).unwrap();
+        praeda_string_free(json_ptr);
+        assert_eq!(issues.len(), issue_count as usize);
+        assert!(issues.iter().any(|issue| issue.contains("No qualities")));
+        assert!(issues.iter().any(|issue| issue.contains("weapon/sword")));
+
+        let _ = praeda_generator_set_quality_data(handle, CString::new("common").unwrap().as_ptr(), 1);
+        let _ = praeda_generator_set_attribute(
+            handle,
+            CString::new("weapon").unwrap().as_ptr(),
+            CString::new("").unwrap().as_ptr(),
+            CString::new("damage").unwrap().as_ptr(),
+            1.0,
+            0.0,
+            10.0,
+            1,
+        );
+        let mut json_ptr = std::ptr::null_mut();
+        assert_eq!(praeda_generator_validate(handle, &mut json_ptr), 0);
+        assert_eq!(c_str_to_string(json_ptr), "[]");
+        praeda_string_free(json_ptr);
+
+        assert_eq!(praeda_generator_validate(std::ptr::null(), std::ptr::null_mut()), -1);
+        praeda_generator_free(handle);
+    }
+}
+
+#[test]
+fn test_generate_loot_json_pretty() {
+    unsafe {
+        let handle = configured_generator();
+
+        let mut error_ptr = std::ptr::null_mut();
+        let json_ptr = praeda_generator_generate_loot_json_pretty(handle, 2, 5.0, 0.0, 0.0, 1, 1.0, &mut error_ptr);
+        assert!(!json_ptr.is_null());
+        assert!(error_ptr.is_null());
+
+        let json = c_str_to_string(json_ptr);
+        assert!(json.contains('\n'));
+        let items: Vec<praeda::Item> = serde_json::from_str(&json).unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(|item| item.get_subtype() == "sword"));
+
+        praeda_string_free(json_ptr);
+        praeda_generator_free(handle);
+    }
+}
@@ -151,6 +151,7 @@ fn test_single_item_generation() -> Result<()> {
         affix_chance: 0.5,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
@@ -178,6 +179,7 @@ fn test_multiple_items_generation() -> Result<()> {
         affix_chance: 0.25,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "bulk")?;
@@ -212,6 +214,89 @@ fn test_quality_override() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_quality_whitelist() -> Result<()> {
+    let mut generator = create_test_generator();
+
+    let options = GeneratorOptions {
+        number_of_items: 300,
+        ..Default::default()
+    };
+    let overrides = GeneratorOverrides {
+        quality_whitelist: vec!["uncommon".to_string(), "rare".to_string()],
+        ..GeneratorOverrides::empty()
+    };
+
+    let items = generator.generate_loot(&options, &overrides, "whitelist")?;
+    assert!(items.iter().all(|item| item.get_quality() != "common"));
+    assert!(items.iter().any(|item| item.get_quality() == "uncommon"));
+    assert!(items.iter().any(|item| item.get_quality() == "rare"));
+
+    let overrides = GeneratorOverrides {
+        quality_whitelist: vec!["legendary".to_string()],
+        ..GeneratorOverrides::empty()
+    };
+    assert!(generator.generate_loot(&options, &overrides, "none").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_quality_whitelist_of_zero_weights_errors() {
+    let mut generator = create_test_generator();
+    generator.set_quality_data("cursed", 0);
+    let options = GeneratorOptions { number_of_items: 5, ..Default::default() };
+    let overrides = GeneratorOverrides {
+        quality_whitelist: vec!["cursed".to_string()],
+        ..GeneratorOverrides::empty()
+    };
+    assert!(matches!(
+        generator.generate_loot(&options, &overrides, "zero"),
+        Err(PraedaError::InvalidData(_))
+    ));
+
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data_f64("common", 1.0);
+    generator.set_quality_data_f64("cursed", 0.0);
+    generator.set_item_type("weapon", 1);
+    generator.set_item_subtype("weapon", "sword", 1);
+    assert!(generator.generate_loot(&options, &GeneratorOverrides::empty(), "ok").is_ok());
+    let overrides = GeneratorOverrides {
+        quality_whitelist: vec!["cursed".to_string()],
+        ..GeneratorOverrides::empty()
+    };
+    assert!(matches!(
+        generator.generate_loot(&options, &overrides, "zero"),
+        Err(PraedaError::InvalidData(_))
+    ));
+}
+
+#[test]
+fn test_zero_weight_type_never_drops() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    // Created only through its subtype, so the type has weight 0
+    generator.set_item_subtype("trinket", "charm", 1);
+
+    let options = GeneratorOptions {
+        number_of_items: 200,
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert!(items.iter().all(|item| item.get_type() == "weapon"));
+
+    // Forcing the type still works
+    let items = generator.generate_loot(&options, &GeneratorOverrides::new("", "trinket", ""), "forced")?;
+    assert!(items.iter().all(|item| item.get_type() == "trinket"));
+
+    let mut only_zero = PraedaGenerator::new();
+    only_zero.set_quality_data("common", 1);
+    only_zero.set_item_subtype("trinket", "charm", 1);
+    let result = only_zero.generate_loot(&options, &GeneratorOverrides::empty(), "test");
+    assert!(matches!(result, Err(PraedaError::InvalidData(_))));
+
+    Ok(())
+}
+
 #[test]
 fn test_type_override() -> Result<()> {
     let mut generator = create_test_generator();
@@ -262,6 +347,7 @@ fn test_linear_vs_exponential_scaling() -> Result<()> {
         affix_chance: 1.0, // Set to 1.0 to ensure optional attributes are applied
         linear: true,
         scaling_factor: 1.5,
+        ..Default::default()
     };
 
     let exp_opts = GeneratorOptions {
@@ -271,6 +357,7 @@ fn test_linear_vs_exponential_scaling() -> Result<()> {
         affix_chance: 1.0, // Set to 1.0 to ensure optional attributes are applied
         linear: false,
         scaling_factor: 1.5,
+        ..Default::default()
     };
 
     let linear_items = gen1.generate_loot(&linear_opts, &GeneratorOverrides::empty(), "linear")?;
@@ -298,6 +385,7 @@ fn test_json_serialization() -> Result<()> {
         affix_chance: 0.25,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "json_test")?;
@@ -321,6 +409,7 @@ fn test_affixes_applied_to_items() -> Result<()> {
         affix_chance: 1.0, // Always apply affixes
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "affix_test")?;
@@ -512,6 +601,7 @@ fn test_quality_distribution() -> Result<()> {
         affix_chance: 0.0,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "distribution")?;
@@ -560,6 +650,7 @@ fn test_quality_weights_respect_ratios() -> Result<()> {
         affix_chance: 0.0,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "weight_test")?;
@@ -623,6 +714,7 @@ fn test_item_type_weights() -> Result<()> {
         affix_chance: 0.0,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "type_weights")?;
@@ -682,6 +774,7 @@ fn test_subtype_weights() -> Result<()> {
         affix_chance: 0.0,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "subtype_weights")?;
@@ -814,6 +907,7 @@ fn test_exponential_scaling_variance() -> Result<()> {
         affix_chance: 0.3,
         linear: false, // Exponential scaling
         scaling_factor: 1.5,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "exp_scaling")?;
@@ -870,6 +964,7 @@ fn test_minimal_single_item_generation() -> Result<()> {
         affix_chance: 0.0,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "minimal")?;
@@ -944,6 +1039,7 @@ fn test_extreme_weight_skew() -> Result<()> {
         affix_chance: 0.0,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "skew")?;
@@ -1009,6 +1105,7 @@ fn test_many_item_types() -> Result<()> {
         affix_chance: 0.2,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "many_types")?;
@@ -1148,6 +1245,7 @@ fn test_full_rpg_loot_scenario() -> Result<()> {
         affix_chance: 0.25,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "rpg_loot")?;
@@ -1211,6 +1309,7 @@ fn test_linear_vs_exponential_scaling_comparison() -> Result<()> {
         affix_chance: 0.0,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items_linear = generator.generate_loot(&options_linear, &GeneratorOverrides::empty(), "linear")?;
@@ -1223,6 +1322,7 @@ fn test_linear_vs_exponential_scaling_comparison() -> Result<()> {
         affix_chance: 0.0,
         linear: false,
         scaling_factor: 1.5,
+        ..Default::default()
     };
 
     let items_exp = generator.generate_loot(&options_exp, &GeneratorOverrides::empty(), "exp")?;
@@ -1322,6 +1422,7 @@ fn test_override_cascade() -> Result<()> {
         affix_chance: 0.0,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator.generate_loot(&options, &overrides, "overrides")?;
@@ -1356,6 +1457,158 @@ fn test_load_toml_data() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_reload_data_preserves_loot() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 3,
+        ..Default::default()
+    };
+    let issued = generator.generate_loot(&options, &GeneratorOverrides::empty(), "issued")?;
+
+    generator.reload_data_from_file("examples/test_data.toml")?;
+
+    // Config was replaced rather than merged
+    assert!(generator.has_quality("epic"));
+    assert!(!generator.has_item_subtype("weapon", "sword"));
+    assert!(generator.has_item_subtype("weapon", "one-handed"));
+
+    let stored = generator.get_loot("issued");
+    assert_eq!(stored.len(), issued.len());
+    for (a, b) in stored.iter().zip(issued.iter()) {
+        assert_eq!(a.get_name(), b.get_name());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_reload_data_keeps_setter_config() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_store_loot(false);
+    generator.set_max_items(5);
+    generator.set_name_fallback(NameFallback::SubtypeWithType);
+    generator.set_item_name_metadata(
+        "weapon",
+        "sword",
+        "Excalibur",
+        "lore",
+        serde_json::json!("Drawn from stone"),
+    );
+
+    generator.reload_data_from_file("examples/test_data.toml")?;
+
+    assert!(!generator.get_store_loot());
+    assert_eq!(generator.get_max_items(), 5);
+    assert!(matches!(
+        generator.get_name_fallback(),
+        NameFallback::SubtypeWithType
+    ));
+    // Tables a TOML file describes are still cleared
+    assert!(!generator.has_item_subtype("weapon", "sword"));
+    assert!(
+        generator
+            .get_item_name_metadata("weapon", "sword", "Excalibur", "lore")
+            .is_none()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_reload_data_invalid_keeps_config() {
+    let mut generator = create_test_generator();
+    assert!(generator.reload_data("not [valid toml").is_err());
+    assert!(generator.has_quality("common"));
+    assert!(generator.has_item_type("weapon"));
+}
+
+#[test]
+fn test_item_name_metadata_keys() {
+    let mut generator = create_test_generator();
+    generator.set_item_name_metadata("weapon", "sword", "Excalibur", "lore", serde_json::json!("Drawn from stone"));
+    generator.set_item_name_metadata("weapon", "sword", "Excalibur", "icon", serde_json::json!("excalibur.png"));
+    generator.set_item_name_metadata("armor", "head", "Crown", "icon", serde_json::json!("crown.png"));
+
+    assert_eq!(
+        generator.get_item_name_metadata_keys("weapon", "sword", "Excalibur"),
+        vec!["icon".to_string(), "lore".to_string()]
+    );
+    assert!(generator.get_item_name_metadata_keys("weapon", "sword", "Dagger").is_empty());
+
+    let mut entries: Vec<(&str, &str, &str, usize)> = generator
+        .iter_item_name_metadata()
+        .map(|((item_type, subtype, name), metadata)| (item_type, subtype, name, metadata.len()))
+        .collect();
+    entries.sort();
+    assert_eq!(entries, vec![("armor", "head", "Crown", 1), ("weapon", "sword", "Excalibur", 2)]);
+}
+
+#[test]
+fn test_get_config_snapshot() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_item("weapon", "sword", vec!["Longsword"]);
+    generator.set_prefix_attribute("weapon", "sword", "Sharp", ItemAttribute::new("damage", 2.0, 0.0, 5.0, true));
+    generator.set_subtype_metadata("weapon", "sword", "two_handed", serde_json::json!(false));
+    generator.set_quality_metadata("rare", "color", serde_json::json!("#0070dd"));
+
+    let snapshot = generator.get_config_snapshot();
+    assert_eq!(snapshot.quality_data.get("rare"), Some(&30));
+    assert_eq!(snapshot.quality_metadata["rare"]["color"], serde_json::json!("#0070dd"));
+
+    let weapon = snapshot.item_types.iter().find(|t| t.item_type == "weapon").unwrap();
+    assert_eq!(weapon.subtypes.get("axe"), Some(&1));
+
+    let attributes: Vec<(&str, &str)> = snapshot
+        .item_attributes
+        .iter()
+        .map(|a| (a.item_type.as_str(), a.subtype.as_str()))
+        .collect();
+    assert_eq!(attributes, vec![("armor", ""), ("weapon", "")]);
+
+    let swords = snapshot.item_list.iter().find(|l| l.subtype == "sword").unwrap();
+    assert_eq!(swords.names, vec!["Longsword".to_string()]);
+
+    let sword_affixes = snapshot
+        .item_affixes
+        .iter()
+        .find(|a| a.item_type == "weapon" && a.subtype == "sword")
+        .unwrap();
+    assert_eq!(sword_affixes.prefixes[0].get_name(), "Sharp");
+    assert_eq!(sword_affixes.metadata["two_handed"], serde_json::json!(false));
+
+    // The snapshot reads back through load_data
+    let mut reloaded = PraedaGenerator::new();
+    reloaded.load_data(&toml::to_string(&snapshot).unwrap())?;
+    assert_eq!(reloaded.get_config_snapshot().item_affixes, snapshot.item_affixes);
+
+    Ok(())
+}
+
+#[test]
+fn test_config_eq() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_item("weapon", "sword", vec!["Longsword"]);
+    generator.set_prefix_attribute("weapon", "sword", "Sharp", ItemAttribute::new("damage", 2.0, 0.0, 5.0, true));
+
+    let mut copy = generator.clone();
+    assert!(generator.config_eq(&copy));
+
+    // Stored loot is not part of the configuration
+    copy.generate_loot(&GeneratorOptions::default(), &GeneratorOverrides::empty(), "loot")?;
+    assert!(generator.config_eq(&copy));
+
+    copy.set_quality_data("legendary", 1);
+    assert!(!generator.config_eq(&copy));
+
+    // A TOML round trip through the config snapshot keeps the configuration
+    let mut reloaded = PraedaGenerator::new();
+    reloaded.load_data(&toml::to_string(&generator.get_config_snapshot()).unwrap())?;
+    assert!(generator.config_eq(&reloaded));
+
+    Ok(())
+}
+
 #[test]
 fn test_generate_loot_json() -> Result<()> {
     let mut generator = create_test_generator();
@@ -1367,6 +1620,7 @@ fn test_generate_loot_json() -> Result<()> {
         affix_chance: 0.25,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let json_str = generator.generate_loot_json(&options, &GeneratorOverrides::empty(), "json_gen")?;
@@ -1621,6 +1875,154 @@ fn test_attribute_generate_value_exponential() {
     assert!((attr.get_initial_value() - expected).abs() < 0.01);
 }
 
+#[test]
+fn test_logarithmic_scaling_diminishes() {
+    let value_at = |level: f64| {
+        let mut attr = ItemAttribute::new("crit", 1.0, 0.0, 100.0, true);
+        attr.generate_value_with_curve(level, ScalingCurve::Logarithmic, 2.0);
+        attr.get_initial_value()
+    };
+
+    assert!((value_at(10.0) - (1.0 + 2.0 * 11.0_f64.ln())).abs() < 1e-9);
+
+    let increments: Vec<f64> = (1..10).map(|level| value_at(level as f64) - value_at(level as f64 - 1.0)).collect();
+    for pair in increments.windows(2) {
+        assert!(pair[1] > 0.0);
+        assert!(pair[1] < pair[0]);
+    }
+}
+
+#[test]
+fn test_sigmoid_scaling_caps_at_scaling_factor() {
+    let curve = ScalingCurve::Sigmoid { midpoint: 10.0, steepness: 1.0 };
+    let value_at = |level: f64| {
+        let mut attr = ItemAttribute::new("crit", 0.0, 0.0, 100.0, true);
+        attr.generate_value_with_curve(level, curve, 20.0);
+        attr.get_initial_value()
+    };
+
+    assert!((value_at(10.0) - 10.0).abs() < 1e-9);
+    assert!(value_at(40.0) < 20.0);
+    assert!(value_at(40.0) > 19.9);
+}
+
+#[test]
+fn test_scaling_curve_maps_linear_flag() {
+    let mut options = GeneratorOptions::default();
+    assert_eq!(options.scaling_curve(), ScalingCurve::Linear);
+    assert!(options.is_linear());
+
+    options.linear = false;
+    assert_eq!(options.scaling_curve(), ScalingCurve::Exponential);
+    assert!(options.is_exponential());
+
+    options.curve = Some(ScalingCurve::Logarithmic);
+    assert!(!options.is_linear());
+    assert!(!options.is_exponential());
+}
+
+#[test]
+fn test_generate_loot_with_logarithmic_curve() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 5.0, 0.0, 100.0, true));
+
+    let options = GeneratorOptions {
+        curve: Some(ScalingCurve::Logarithmic),
+        scaling_factor: 3.0,
+        ..merge_options()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let damage = items[0].get_attribute("damage").unwrap().get_initial_value();
+    assert!((damage - (5.0 + 3.0 * 11.0_f64.ln())).abs() < 1e-9);
+
+    Ok(())
+}
+
+#[test]
+fn test_exponential_scaling_overflow_stays_finite() -> Result<()> {
+    let mut unbounded = ItemAttribute::new("damage", 10.0, 0.0, 0.0, true);
+    unbounded.generate_value(2000.0, false, 2.0);
+    assert!(unbounded.get_initial_value().is_finite());
+    assert_eq!(unbounded.get_initial_value(), f64::MAX);
+
+    let mut bounded = ItemAttribute::new("damage", 10.0, 5.0, 500.0, true);
+    bounded.generate_value(2000.0, false, 2.0);
+    assert_eq!(bounded.get_initial_value(), 500.0);
+
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 10.0, 0.0, 0.0, true));
+    let options = GeneratorOptions {
+        base_level: 2000.0,
+        linear: false,
+        scaling_factor: 2.0,
+        ..merge_options()
+    };
+    let json = generator.generate_loot_json(&options, &GeneratorOverrides::empty(), "test")?;
+    let items: Vec<Item> = serde_json::from_str(&json)?;
+    assert!(items[0].get_attribute("damage").unwrap().get_initial_value().is_finite());
+
+    Ok(())
+}
+
+#[test]
+fn test_scaling_overflow_and_nan_are_handled() {
+    // Overflow towards negative infinity saturates instead of staying infinite
+    let mut sinking = ItemAttribute::new("damage", -1.0, 0.0, 0.0, true);
+    sinking.generate_value_with_curve(2.0, ScalingCurve::Linear, -f64::MAX);
+    assert!(sinking.get_initial_value().is_finite());
+
+    // A NaN base value on a required attribute is rejected rather than stored
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute(
+        "weapon",
+        "sword",
+        ItemAttribute::new("damage", f64::NAN, 0.0, 0.0, true),
+    );
+    let result = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test");
+    assert!(matches!(result, Err(PraedaError::InvalidData(_))));
+}
+
+#[test]
+fn test_affix_overflow_is_rejected() {
+    // A multiplier that overflows an unbounded attribute
+    let mut generator = create_attribute_merge_generator();
+    let mut damage = ItemAttribute::new("damage", 1e300, 0.0, 0.0, true);
+    damage.set_scaling_factor(0.0);
+    generator.set_attribute("weapon", "sword", damage);
+    let mut multiplier = ItemAttribute::new("damage", 1e10, 0.0, 0.0, true);
+    multiplier.set_apply_mode(ApplyMode::Multiply);
+    generator.set_prefix_attribute("weapon", "sword", "Brutal", multiplier);
+
+    let options = GeneratorOptions {
+        scaling_factor: 0.0,
+        ..merge_options()
+    };
+    let result = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test");
+    assert!(matches!(result, Err(PraedaError::InvalidData(_))));
+
+    // Additive affixes that overflow towards negative infinity, with no floor to stop them
+    let mut generator = create_attribute_merge_generator();
+    generator.set_prefix_attribute(
+        "weapon",
+        "sword",
+        "Cursed",
+        ItemAttribute::new("damage", -1e308, 0.0, 0.0, true),
+    );
+    generator.set_suffix_attribute(
+        "weapon",
+        "sword",
+        "of Ruin",
+        ItemAttribute::new("damage", -1e308, 0.0, 0.0, true),
+    );
+    let options = GeneratorOptions {
+        attribute_floor: None,
+        ..merge_options()
+    };
+    let result = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test");
+    assert!(matches!(result, Err(PraedaError::InvalidData(_))));
+}
+
 // ============================================================================
 // EDGE CASES AND ERROR HANDLING
 // ============================================================================
@@ -1971,6 +2373,7 @@ fn test_generated_item_contains_subtype_metadata() {
         affix_chance: 0.0,
         linear: true,
         scaling_factor: 1.0,
+        ..Default::default()
     };
 
     let items = generator
@@ -2033,3 +2436,2985 @@ rarity_multiplier = 1.5
     assert_eq!(multiplier.unwrap(), &serde_json::json!(1.5));
 }
 
+
+#[test]
+fn test_large_batch_generation() -> Result<()> {
+    let mut generator = create_test_generator();
+
+    let options = GeneratorOptions {
+        number_of_items: 100_000,
+        base_level: 10.0,
+        level_variance: 5.0,
+        affix_chance: 0.5,
+        linear: true,
+        scaling_factor: 1.0,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "large")?;
+    assert_eq!(items.len(), 100_000);
+    assert_eq!(generator.get_loot("large").len(), 100_000);
+
+    // Affix attributes must still be merged into the item after the affix is applied
+    for item in &items {
+        for affix in [item.get_prefix(), item.get_suffix()] {
+            for attr in affix.get_attributes() {
+                assert!(item.has_attribute(attr.get_name()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_requirement_detection_uses_suffix_match() -> Result<()> {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 1);
+    generator.set_item_type("weapon", 1);
+    generator.set_item_subtype("weapon", "sword", 1);
+
+    generator.set_attribute(
+        "weapon",
+        "sword",
+        ItemAttribute::new("power_requirement_bonus", 3.0, 0.0, 0.0, true),
+    );
+    generator.set_attribute(
+        "weapon",
+        "sword",
+        ItemAttribute::new("strength_requirement", 0.0, 0.0, 0.0, true),
+    );
+
+    let options = GeneratorOptions {
+        number_of_items: 1,
+        base_level: 50.0,
+        level_variance: 0.0,
+        affix_chance: 0.0,
+        linear: true,
+        scaling_factor: 1.0,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let item = &items[0];
+
+    // Scaled normally: 3.0 + 50 * 1.0, not forced to the level
+    let bonus = item.get_attribute("power_requirement_bonus").unwrap();
+    assert_eq!(bonus.get_initial_value(), 53.0);
+
+    let requirement = item.get_attribute("strength_requirement").unwrap();
+    assert_eq!(requirement.get_initial_value(), 50.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_requirement_flag_sets_level_value() -> Result<()> {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 1);
+    generator.set_item_type("weapon", 1);
+    generator.set_item_subtype("weapon", "sword", 1);
+
+    generator.set_attribute(
+        "weapon",
+        "sword",
+        ItemAttribute::new_requirement("min_strength", true),
+    );
+
+    let options = GeneratorOptions {
+        number_of_items: 1,
+        base_level: 25.0,
+        level_variance: 0.0,
+        affix_chance: 0.0,
+        linear: true,
+        scaling_factor: 2.0,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let attr = items[0].get_attribute("min_strength").unwrap();
+    assert!(attr.get_is_requirement());
+    assert_eq!(attr.get_initial_value(), 25.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_requirement_flag_from_toml() -> Result<()> {
+    let toml_str = r#"
+[quality_data]
+common = 1
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+
+[[item_attributes]]
+item_type = "weapon"
+subtype = "sword"
+[[item_attributes.attributes]]
+name = "min_dexterity"
+initial_value = 0.0
+min = 0.0
+max = 0.0
+required = true
+is_requirement = true
+    "#;
+
+    let mut generator = PraedaGenerator::new();
+    generator.load_data(toml_str)?;
+
+    let options = GeneratorOptions {
+        number_of_items: 1,
+        base_level: 12.0,
+        level_variance: 0.0,
+        affix_chance: 0.0,
+        linear: true,
+        scaling_factor: 1.0,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(items[0].get_attribute("min_dexterity").unwrap().get_initial_value(), 12.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_affix_chance_scales_with_level() -> Result<()> {
+    let mut generator = create_test_generator();
+    let overrides = GeneratorOverrides::new("", "weapon", "");
+
+    let count_affixes = |items: &[Item]| {
+        items
+            .iter()
+            .filter(|item| !item.get_prefix().get_name().is_empty() || !item.get_suffix().get_name().is_empty())
+            .count()
+    };
+
+    let low_options = GeneratorOptions {
+        number_of_items: 1000,
+        base_level: 1.0,
+        level_variance: 0.0,
+        affix_chance: 0.0,
+        affix_chance_per_level: 0.01,
+        ..Default::default()
+    };
+    let low_items = generator.generate_loot(&low_options, &overrides, "low")?;
+
+    let high_options = GeneratorOptions {
+        base_level: 90.0,
+        ..low_options.clone()
+    };
+    let high_items = generator.generate_loot(&high_options, &overrides, "high")?;
+
+    assert!(
+        count_affixes(&high_items) > count_affixes(&low_items) * 5,
+        "high level items should roll affixes far more often"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_optional_attribute_chance_scales_with_level() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute(
+        "weapon",
+        "sword",
+        ItemAttribute::new("fire", 3.0, 0.0, 20.0, false),
+    );
+
+    // A base chance of 0 reaches 1.0 at level 10
+    let options = GeneratorOptions {
+        number_of_items: 20,
+        affix_chance: 0.0,
+        affix_chance_per_level: 0.1,
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert!(items.iter().all(|item| item.has_attribute("fire")));
+
+    Ok(())
+}
+
+#[test]
+fn test_affix_chance_at_level_is_clamped() {
+    let options = GeneratorOptions {
+        affix_chance: 0.5,
+        affix_chance_per_level: 0.1,
+        ..Default::default()
+    };
+
+    assert_eq!(options.affix_chance_at_level(0.0), 0.5);
+    assert_eq!(options.affix_chance_at_level(100.0), 1.0);
+    assert_eq!(options.affix_chance_at_level(-100.0), 0.0);
+}
+
+#[test]
+fn test_load_nested_metadata_from_toml() -> Result<()> {
+    let toml_str = r#"
+[quality_data]
+common = 100
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+
+[[item_affixes]]
+item_type = "weapon"
+subtype = "sword"
+[item_affixes.metadata]
+tags = ["melee", "sharp"]
+requirements = { str = 10, dex = 5 }
+[item_affixes.metadata.sockets]
+max = 3
+colors = ["red", "blue"]
+    "#;
+
+    let mut generator = PraedaGenerator::new();
+    generator.load_data(toml_str)?;
+
+    let tags = generator.get_subtype_metadata("weapon", "sword", "tags").unwrap();
+    assert_eq!(tags, &serde_json::json!(["melee", "sharp"]));
+    assert!(tags.is_array());
+
+    let requirements = generator.get_subtype_metadata("weapon", "sword", "requirements").unwrap();
+    assert_eq!(requirements, &serde_json::json!({ "str": 10, "dex": 5 }));
+    assert!(requirements.is_object());
+
+    let sockets = generator.get_subtype_metadata("weapon", "sword", "sockets").unwrap();
+    assert_eq!(sockets["max"], serde_json::json!(3));
+    assert_eq!(sockets["colors"], serde_json::json!(["red", "blue"]));
+
+    // Nested values are carried onto generated items unchanged
+    let options = GeneratorOptions {
+        number_of_items: 1,
+        affix_chance: 0.0,
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(items[0].get_metadata("tags"), Some(&serde_json::json!(["melee", "sharp"])));
+
+    Ok(())
+}
+
+#[test]
+fn test_quality_specific_item_names() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_quality_data("legendary", 1);
+    generator.set_item_for_quality("weapon", "sword", "legendary", vec!["Excalibur", "Durendal"]);
+
+    assert_eq!(
+        generator.get_item_names_for_quality("weapon", "sword", "legendary"),
+        vec!["Excalibur".to_string(), "Durendal".to_string()]
+    );
+
+    let options = GeneratorOptions {
+        number_of_items: 50,
+        ..Default::default()
+    };
+
+    let legendary = generator.generate_loot(
+        &options,
+        &GeneratorOverrides::new("legendary", "weapon", "sword"),
+        "legendary",
+    )?;
+    for item in &legendary {
+        assert!(
+            item.get_name() == "Excalibur" || item.get_name() == "Durendal",
+            "legendary swords should only use the legendary pool, got {}",
+            item.get_name()
+        );
+    }
+
+    // Other qualities keep using the general list
+    let common = generator.generate_loot(
+        &options,
+        &GeneratorOverrides::new("common", "weapon", "sword"),
+        "common",
+    )?;
+    for item in &common {
+        assert!(item.get_name() == "longsword" || item.get_name() == "shortsword");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_loot_with_hook() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 5,
+        ..Default::default()
+    };
+
+    let mut calls = 0;
+    let items = generator.generate_loot_with_hook(
+        &options,
+        &GeneratorOverrides::empty(),
+        "hooked",
+        &mut |item| {
+            calls += 1;
+            item.set_metadata("enchanted", serde_json::json!(true));
+        },
+    )?;
+
+    assert_eq!(calls, 5);
+    assert!(items.iter().all(|item| item.has_metadata("enchanted")));
+
+    let stored = generator.get_loot("hooked");
+    assert_eq!(stored.len(), 5);
+    for item in &stored {
+        assert_eq!(item.get_metadata("enchanted"), Some(&serde_json::json!(true)));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_take_loot_item() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 3,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "chest")?;
+    assert_eq!(generator.loot_remaining("chest"), 3);
+
+    for expected_remaining in (0..3).rev() {
+        let item = generator.take_loot_item("chest").unwrap();
+        assert_eq!(item, items[expected_remaining]);
+        assert_eq!(generator.loot_remaining("chest"), expected_remaining);
+    }
+
+    assert!(generator.take_loot_item("chest").is_none());
+    assert!(generator.get_loot("chest").is_empty());
+    assert_eq!(generator.loot_remaining("missing"), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_custom_level_attribute_name() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 3,
+        level_attribute_name: "ilvl".to_string(),
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    for item in &items {
+        assert!(item.has_attribute("ilvl"));
+        assert!(!item.has_attribute("level"));
+        assert_eq!(item.get_attribute("ilvl").unwrap().get_name(), "ilvl");
+    }
+
+    assert_eq!(GeneratorOptions::default().level_attribute_name, "level");
+
+    Ok(())
+}
+
+#[test]
+fn test_unknown_overrides_return_errors() {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions::default();
+
+    let result = generator.generate_loot(&options, &GeneratorOverrides::new("mythic", "", ""), "test");
+    assert!(matches!(result, Err(PraedaError::MissingQuality(q)) if q == "mythic"));
+
+    let result = generator.generate_loot(&options, &GeneratorOverrides::new("", "trinket", ""), "test");
+    assert!(matches!(result, Err(PraedaError::MissingItemType(t)) if t == "trinket"));
+
+    let result = generator.generate_loot(&options, &GeneratorOverrides::new("", "weapon", "spear"), "test");
+    assert!(matches!(
+        result,
+        Err(PraedaError::MissingItemSubtype(t, s)) if t == "weapon" && s == "spear"
+    ));
+
+    // Nothing is stored for a failed generation
+    assert!(generator.get_loot("test").is_empty());
+}
+
+#[test]
+fn test_luck_increases_rarest_quality_share() -> Result<()> {
+    let mut generator = create_test_generator();
+    let overrides = GeneratorOverrides::new("", "weapon", "");
+
+    let rare_share = |items: &[Item]| {
+        items.iter().filter(|item| item.get_quality() == "rare").count() as f64 / items.len() as f64
+    };
+
+    let unlucky = GeneratorOptions {
+        number_of_items: 2000,
+        ..Default::default()
+    };
+    let base_items = generator.generate_loot(&unlucky, &overrides, "base")?;
+
+    let lucky = GeneratorOptions {
+        luck: 3.0,
+        ..unlucky.clone()
+    };
+    let lucky_items = generator.generate_loot(&lucky, &overrides, "lucky")?;
+
+    // rare: 30/190 (~16%) without luck; with luck = 3.0 uncommon is x2.5 and rare x4,
+    // giving 120/370 (~32%)
+    let base_share = rare_share(&base_items);
+    let lucky_share = rare_share(&lucky_items);
+    assert!(base_share < 0.21, "expected ~16% rare without luck, got {}", base_share * 100.0);
+    assert!(lucky_share > 0.26, "expected ~32% rare with luck, got {}", lucky_share * 100.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_luck_with_large_quality_weights() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_quality_data("common", i32::MAX / 2);
+    generator.set_quality_data("uncommon", i32::MAX / 3);
+    generator.set_quality_data("rare", i32::MAX / 4);
+
+    // Boosted weights far beyond i32 still select a quality
+    let options = GeneratorOptions {
+        number_of_items: 50,
+        luck: 5.0,
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "lucky")?;
+    assert_eq!(items.len(), 50);
+
+    // Without luck a total past i32 is reported instead of overflowing
+    let options = GeneratorOptions {
+        luck: 0.0,
+        ..options
+    };
+    let result = generator.generate_loot(&options, &GeneratorOverrides::empty(), "plain");
+    assert!(matches!(result, Err(PraedaError::InvalidData(_))));
+
+    Ok(())
+}
+
+#[test]
+fn test_seeded_generation_ignores_insertion_order() -> Result<()> {
+    let mut first = PraedaGenerator::new();
+    first.set_quality_data("common", 100);
+    first.set_quality_data("uncommon", 60);
+    first.set_quality_data("rare", 30);
+    first.set_item_type("weapon", 2);
+    first.set_item_type("armor", 1);
+    first.set_item_subtype("weapon", "sword", 3);
+    first.set_item_subtype("weapon", "axe", 2);
+    first.set_item_subtype("armor", "head", 1);
+    first.set_item_subtype("armor", "chest", 1);
+    first.set_item("weapon", "sword", vec!["longsword", "shortsword", "claymore"]);
+    first.set_item("armor", "head", vec!["helm", "crown"]);
+
+    let mut second = PraedaGenerator::new();
+    second.set_quality_data("rare", 30);
+    second.set_quality_data("common", 100);
+    second.set_quality_data("uncommon", 60);
+    second.set_item_type("armor", 1);
+    second.set_item_type("weapon", 2);
+    second.set_item_subtype("armor", "chest", 1);
+    second.set_item_subtype("armor", "head", 1);
+    second.set_item_subtype("weapon", "axe", 2);
+    second.set_item_subtype("weapon", "sword", 3);
+    second.set_item("armor", "head", vec!["crown", "helm"]);
+    second.set_item("weapon", "sword", vec!["claymore", "longsword", "shortsword"]);
+
+    let options = GeneratorOptions {
+        number_of_items: 100,
+        base_level: 10.0,
+        level_variance: 5.0,
+        seed: Some(42),
+        ..Default::default()
+    };
+
+    let first_items = first.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let second_items = second.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(first_items, second_items);
+
+    // Re-running with the same seed reproduces the batch
+    let repeat_items = first.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(first_items, repeat_items);
+
+    Ok(())
+}
+
+#[test]
+fn test_seeded_optional_attributes_ignore_insertion_order() -> Result<()> {
+    let attributes = [
+        ItemAttribute::new("damage", 5.0, 1.0, 50.0, true),
+        ItemAttribute::new("fire", 3.0, 0.0, 20.0, false),
+        ItemAttribute::new("frost", 3.0, 0.0, 20.0, false),
+        ItemAttribute::new("shock", 3.0, 0.0, 20.0, false),
+        ItemAttribute::new("speed", 1.0, 0.0, 5.0, false),
+    ];
+    let build = |order: &[usize]| {
+        let mut generator = create_attribute_merge_generator();
+        for &index in order {
+            generator.set_attribute("weapon", "sword", attributes[index].clone());
+        }
+        generator
+    };
+    let first = build(&[0, 1, 2, 3, 4]);
+    let second = build(&[4, 2, 0, 3, 1]);
+
+    let options = GeneratorOptions {
+        number_of_items: 50,
+        affix_chance: 0.5,
+        seed: Some(7),
+        ..merge_options()
+    };
+    let first_items = first.generate_loot_shared(&options, &GeneratorOverrides::empty())?;
+    let second_items = second.generate_loot_shared(&options, &GeneratorOverrides::empty())?;
+    assert_eq!(first_items, second_items);
+
+    Ok(())
+}
+
+#[test]
+fn test_seeded_name_sequences_repeat() -> Result<()> {
+    let build = || {
+        let mut generator = create_test_generator();
+        generator.set_item("weapon", "sword", vec!["longsword", "shortsword", "claymore", "rapier"]);
+        generator.set_item("weapon", "axe", vec!["hatchet", "battleaxe", "cleaver"]);
+        generator.set_item_for_quality("weapon", "sword", "rare", vec!["moonblade", "sunblade"]);
+        generator
+    };
+    let names = |generator: &mut PraedaGenerator, options: &GeneratorOptions| -> Result<Vec<String>> {
+        let items = generator.generate_loot(options, &GeneratorOverrides::new("", "weapon", ""), "test")?;
+        Ok(items.iter().map(|item| item.get_name().to_string()).collect())
+    };
+
+    for unique_names_within_batch in [false, true] {
+        let options = GeneratorOptions {
+            number_of_items: 60,
+            seed: Some(1151),
+            unique_names_within_batch,
+            ..Default::default()
+        };
+        let first = names(&mut build(), &options)?;
+        assert_eq!(first, names(&mut build(), &options)?);
+
+        let mut distinct = first.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert!(distinct.len() > 3, "seeded runs should still vary names: {:?}", distinct);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn test_msgpack_round_trip() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_subtype_metadata("weapon", "sword", "tags", serde_json::json!(["melee", "sharp"]));
+
+    let options = GeneratorOptions {
+        number_of_items: 25,
+        affix_chance: 0.5,
+        ..Default::default()
+    };
+
+    let bytes = generator.generate_loot_msgpack(&options, &GeneratorOverrides::empty(), "packed")?;
+    let decoded = PraedaGenerator::loot_from_msgpack(&bytes)?;
+
+    assert_eq!(decoded, generator.get_loot("packed"));
+
+    let json = generator.get_loot_json("packed")?;
+    assert!(bytes.len() < json.len(), "MessagePack should be more compact than JSON");
+
+    Ok(())
+}
+
+#[test]
+fn test_name_fallback_strategies() -> Result<()> {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 1);
+    generator.set_item_type("weapon", 1);
+    generator.set_item_subtype("weapon", "sword", 1);
+
+    let options = GeneratorOptions::default();
+    let overrides = GeneratorOverrides::empty();
+
+    assert!(matches!(generator.get_name_fallback(), NameFallback::Subtype));
+    let items = generator.generate_loot(&options, &overrides, "test")?;
+    assert_eq!(items[0].get_name(), "sword");
+
+    generator.set_name_fallback(NameFallback::SubtypeWithType);
+    let items = generator.generate_loot(&options, &overrides, "test")?;
+    assert_eq!(items[0].get_name(), "sword (weapon)");
+
+    generator.set_name_fallback(NameFallback::Custom(std::sync::Arc::new(|item_type, subtype| {
+        format!("Unnamed {} {}", subtype, item_type)
+    })));
+    let items = generator.generate_loot(&options, &overrides, "test")?;
+    assert_eq!(items[0].get_name(), "Unnamed sword weapon");
+
+    // Configured names still take precedence over the fallback
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    let items = generator.generate_loot(&options, &overrides, "test")?;
+    assert_eq!(items[0].get_name(), "longsword");
+
+    Ok(())
+}
+
+#[test]
+fn test_require_names_errors_on_missing_and_empty_names() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_require_names(true);
+    let options = GeneratorOptions::default();
+    let overrides = GeneratorOverrides::empty();
+
+    let result = generator.generate_loot(&options, &overrides, "test");
+    assert!(matches!(result, Err(PraedaError::MissingItemNames(t, s)) if t == "weapon" && s == "sword"));
+
+    generator.set_item("weapon", "sword", vec![]);
+    let result = generator.generate_loot(&options, &overrides, "test");
+    assert!(matches!(result, Err(PraedaError::EmptyItemNames(t, s)) if t == "weapon" && s == "sword"));
+
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    let items = generator.generate_loot(&options, &overrides, "test")?;
+    assert_eq!(items[0].get_name(), "longsword");
+
+    // Without strict mode an empty list still falls back to the subtype
+    generator.set_require_names(false);
+    generator.set_item("weapon", "sword", vec![]);
+    let items = generator.generate_loot(&options, &overrides, "test")?;
+    assert_eq!(items[0].get_name(), "sword");
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_breakdown_sums_to_total() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 200,
+        affix_chance: 1.0,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::new("", "weapon", ""), "test")?;
+
+    let mut saw_affix_source = false;
+    for item in &items {
+        for (name, attr) in item.get_attributes() {
+            let breakdown = item.get_attribute_breakdown(name).unwrap();
+            let total: f64 = breakdown.iter().map(|(_, amount)| amount).sum();
+            assert!((total - attr.get_initial_value()).abs() < 1e-9, "{} breakdown {:?} != {}", name, breakdown, attr.get_initial_value());
+
+            if let Some((_, amount)) = breakdown.iter().find(|(source, _)| source == "sharp") {
+                assert_eq!(*amount, 5.0);
+                assert_eq!(breakdown[0].0, BASE_ATTRIBUTE_SOURCE);
+                saw_affix_source = true;
+            }
+        }
+    }
+    assert!(saw_affix_source, "expected at least one sharp prefix contribution");
+
+    Ok(())
+}
+
+#[test]
+fn test_affix_contribution_clamped_to_base_max() -> Result<()> {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 1);
+    generator.set_item_type("weapon", 1);
+    generator.set_item_subtype("weapon", "sword", 1);
+
+    // Base damage already sits at its max after scaling at level 0
+    generator.set_attribute(
+        "weapon",
+        "sword",
+        ItemAttribute::new("damage", 20.0, 1.0, 20.0, true),
+    );
+    generator.set_prefix_attribute(
+        "weapon",
+        "sword",
+        "Flaming",
+        ItemAttribute::new("damage", 10.0, 0.0, 0.0, false),
+    );
+
+    let options = GeneratorOptions {
+        number_of_items: 1,
+        base_level: 0.0,
+        level_variance: 0.0,
+        affix_chance: 1.0,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let item = &items[0];
+    assert_eq!(item.get_prefix().get_name(), "Flaming");
+    assert_eq!(item.get_attribute("damage").unwrap().get_initial_value(), 20.0);
+
+    let breakdown = item.get_attribute_breakdown("damage").unwrap();
+    let total: f64 = breakdown.iter().map(|(_, amount)| amount).sum();
+    assert_eq!(total, 20.0);
+    assert!(breakdown.iter().any(|(source, amount)| source == CLAMP_ATTRIBUTE_SOURCE && *amount == -10.0));
+
+    Ok(())
+}
+
+#[test]
+fn test_positive_affix_never_lowers_scaled_base() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    // Level scaling puts base damage far above its configured max
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 10.0, 1.0, 20.0, true));
+    generator.set_prefix_attribute("weapon", "sword", "Sharp", ItemAttribute::new("damage", 5.0, 0.0, 0.0, false));
+
+    let options = GeneratorOptions { base_level: 50.0, ..merge_options() };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let item = &items[0];
+    assert_eq!(item.get_prefix().get_name(), "Sharp");
+
+    let breakdown = item.get_attribute_breakdown("damage").unwrap();
+    let base: f64 = breakdown
+        .iter()
+        .filter(|(source, _)| source == BASE_ATTRIBUTE_SOURCE)
+        .map(|(_, amount)| amount)
+        .sum();
+    assert_eq!(base, 60.0);
+    // Over the max already, so the prefix adds nothing, but it never pulls the value down
+    assert_eq!(item.get_attribute("damage").unwrap().get_initial_value(), 60.0);
+
+    Ok(())
+}
+
+/// Generator with a single weapon/sword whose optional and affix attributes always apply
+fn create_attribute_merge_generator() -> PraedaGenerator {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 1);
+    generator.set_item_type("weapon", 1);
+    generator.set_item_subtype("weapon", "sword", 1);
+    generator
+}
+
+fn merge_options() -> GeneratorOptions {
+    GeneratorOptions {
+        number_of_items: 1,
+        base_level: 10.0,
+        level_variance: 0.0,
+        affix_chance: 1.0,
+        linear: true,
+        scaling_factor: 1.0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_optional_attribute_new_is_scaled() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("crit", 2.0, 0.0, 0.0, false));
+
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+    // 2.0 + 10 levels * 1.0
+    assert_eq!(items[0].get_attribute("crit").unwrap().get_initial_value(), 12.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_optional_attribute_merges_into_required() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "", ItemAttribute::new("damage", 5.0, 0.0, 0.0, true));
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 3.0, 0.0, 0.0, false));
+
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+    // Required 5.0 + 10 scaled, then the optional 3.0 is added unscaled
+    assert_eq!(items[0].get_attribute("damage").unwrap().get_initial_value(), 18.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_prefix_attribute_new_is_not_scaled() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_prefix_attribute("weapon", "sword", "Swift", ItemAttribute::new("speed", 4.0, 0.0, 0.0, false));
+
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+    let item = &items[0];
+    assert_eq!(item.get_attribute("speed").unwrap().get_initial_value(), 4.0);
+    assert_eq!(
+        item.get_attribute_breakdown("speed").unwrap(),
+        &[("Swift".to_string(), 4.0)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_suffix_attribute_merges_and_requirement_uses_level() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 5.0, 0.0, 0.0, true));
+    generator.set_suffix_attribute("weapon", "sword", "of Might", ItemAttribute::new("damage", 2.0, 0.0, 0.0, false));
+    generator.set_suffix_attribute("weapon", "sword", "of Might", ItemAttribute::new_requirement("strength_needed", false));
+
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+    let item = &items[0];
+    assert_eq!(item.get_attribute("damage").unwrap().get_initial_value(), 17.0);
+    assert_eq!(item.get_attribute("strength_needed").unwrap().get_initial_value(), 10.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_cursed_affix_floors_attribute() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("attack", 5.0, 0.0, 0.0, true));
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("durability", 20.0, 10.0, 50.0, true));
+    generator.set_prefix_attribute("weapon", "sword", "Dull", ItemAttribute::new("attack", -100.0, 0.0, 0.0, false));
+    generator.set_prefix_attribute("weapon", "sword", "Dull", ItemAttribute::new("durability", -100.0, 0.0, 0.0, false));
+
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+    let item = &items[0];
+    assert_eq!(item.get_prefix().get_name(), "Dull");
+
+    // Unbounded attributes floor at 0, bounded ones at their min
+    assert_eq!(item.get_attribute("attack").unwrap().get_initial_value(), 0.0);
+    assert_eq!(item.get_attribute("durability").unwrap().get_initial_value(), 10.0);
+
+    let total: f64 = item.get_attribute_breakdown("attack").unwrap().iter().map(|(_, amount)| amount).sum();
+    assert_eq!(total, 0.0);
+
+    // Disabling the floor lets the curse drive the value negative
+    let options = GeneratorOptions {
+        attribute_floor: None,
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(items[0].get_attribute("attack").unwrap().get_initial_value(), -85.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_count_possible_items() {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 10);
+    generator.set_quality_data("rare", 1);
+    generator.set_quality_data("unused", 0);
+    generator.set_item_type("weapon", 1);
+    generator.set_item_subtype("weapon", "sword", 1);
+    generator.set_item_subtype("weapon", "axe", 1);
+    generator.set_item("weapon", "sword", vec!["longsword", "shortsword", "claymore"]);
+    // axe has no names and falls back to a single name
+
+    // 2 qualities x (3 sword names + 1 axe name)
+    assert_eq!(generator.count_possible_items(), 8);
+
+    // A rare-only name pool replaces the general sword list for that quality
+    generator.set_item_for_quality("weapon", "sword", "rare", vec!["Excalibur"]);
+    // common: 3 + 1, rare: 1 + 1
+    assert_eq!(generator.count_possible_items(), 6);
+
+    generator.set_prefix_attribute("weapon", "", "Sharp", ItemAttribute::new("damage", 1.0, 0.0, 0.0, false));
+    generator.set_prefix_attribute("weapon", "sword", "Keen", ItemAttribute::new("damage", 1.0, 0.0, 0.0, false));
+    generator.set_suffix_attribute("", "", "of Luck", ItemAttribute::new("luck", 1.0, 0.0, 0.0, false));
+
+    // sword: (2 prefixes + none) x (1 suffix + none) = 6 variants, axe: (1 + 1) x (1 + 1) = 4
+    // common: 3 * 6 + 1 * 4 = 22, rare: 1 * 6 + 1 * 4 = 10
+    assert_eq!(generator.count_possible_items_with_affixes(), 32);
+}
+
+#[test]
+fn test_quality_min_affixes() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_quality_data("legendary", 1);
+    generator.set_quality_min_affixes("legendary", 2);
+    assert_eq!(generator.get_quality_min_affixes("legendary"), 2);
+    assert_eq!(generator.get_quality_min_affixes("common"), 0);
+
+    let options = GeneratorOptions {
+        number_of_items: 20,
+        affix_chance: 0.0,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::new("legendary", "weapon", ""), "test")?;
+    for item in &items {
+        assert!(!item.get_prefix().get_name().is_empty());
+        assert!(!item.get_suffix().get_name().is_empty());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_min_affixes_with_empty_pool_errors() {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("legendary", 1);
+    generator.set_item_type("armor", 1);
+    generator.set_item_subtype("armor", "head", 1);
+    generator.set_quality_min_affixes("legendary", 1);
+
+    let options = GeneratorOptions {
+        affix_chance: 0.0,
+        ..Default::default()
+    };
+
+    let result = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test");
+    match result {
+        Err(PraedaError::InvalidData(message)) => {
+            assert!(message.contains("type=armor"), "{}", message);
+            assert!(message.contains("subtype=head"), "{}", message);
+        }
+        other => panic!("expected InvalidData, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_generate_loot_reported() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 12,
+        affix_chance: 1.0,
+        seed: Some(7),
+        ..Default::default()
+    };
+
+    let (items, report) = generator.generate_loot_reported(&options, &GeneratorOverrides::empty(), "report")?;
+    assert_eq!(report.items_generated, items.len());
+    assert_eq!(report.items_generated, 12);
+    assert!(report.rng_calls > 0);
+
+    let affixes: u64 = items
+        .iter()
+        .map(|item| {
+            (!item.get_prefix().get_name().is_empty()) as u64 + (!item.get_suffix().get_name().is_empty()) as u64
+        })
+        .sum();
+    assert_eq!(report.affixes_applied, affixes);
+    assert_eq!(report.unique_name_retries, 0);
+    assert_eq!(generator.get_loot("report").len(), 12);
+
+    // Counting must not change the rolls
+    let plain = generator.generate_loot(&options, &GeneratorOverrides::empty(), "plain")?;
+    for (a, b) in plain.iter().zip(items.iter()) {
+        assert_eq!(a.get_name(), b.get_name());
+        assert_eq!(a.get_quality(), b.get_quality());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_loot_reported_counts_unique_name_retries() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword", "shortsword"]);
+    let options = GeneratorOptions {
+        number_of_items: 5,
+        unique_names_within_batch: true,
+        seed: Some(4),
+        ..merge_options()
+    };
+
+    // Two names cover five items with the initial draw plus two reshuffles
+    let (items, report) = generator.generate_loot_reported(&options, &GeneratorOverrides::empty(), "unique")?;
+    assert_eq!(items.len(), 5);
+    assert_eq!(report.unique_name_retries, 2);
+    assert_eq!(report.affixes_applied, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_suffix_only_affix_slots() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_prefix_attribute("", "", "blessed", ItemAttribute::new("damage", 1.0, 0.0, 5.0, true));
+    generator.set_suffix_attribute("", "", "of vigor", ItemAttribute::new("health", 1.0, 0.0, 5.0, true));
+    generator.set_affix_slots("weapon", false, true);
+    assert_eq!(generator.get_affix_slots("weapon"), (false, true));
+    assert_eq!(generator.get_affix_slots("armor"), (true, true));
+
+    let options = GeneratorOptions {
+        number_of_items: 50,
+        affix_chance: 1.0,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    for item in &items {
+        if item.get_type() == "weapon" {
+            assert!(item.get_prefix().get_name().is_empty());
+            assert!(!item.get_suffix().get_name().is_empty());
+        } else {
+            assert!(!item.get_prefix().get_name().is_empty());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn test_bincode_round_trip() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_subtype_metadata("weapon", "sword", "slot", serde_json::json!({"hand": "main"}));
+    generator.set_quality_min_affixes("rare", 1);
+    generator.set_affix_metadata("weapon", "sword", true, "Soulbound", "soulbound", serde_json::json!(true));
+    generator.set_quality_metadata("rare", "color", serde_json::json!("#0070dd"));
+
+    let options = GeneratorOptions {
+        number_of_items: 10,
+        seed: Some(99),
+        ..Default::default()
+    };
+
+    let bytes = generator.to_bincode()?;
+    let mut restored = PraedaGenerator::from_bincode(&bytes)?;
+    assert_eq!(restored.get_quality_min_affixes("rare"), 1);
+    assert_eq!(restored.get_prefixes("weapon", "sword"), generator.get_prefixes("weapon", "sword"));
+
+    let before = generator.generate_loot(&options, &GeneratorOverrides::empty(), "before")?;
+    let after = restored.generate_loot(&options, &GeneratorOverrides::empty(), "after")?;
+    assert_eq!(before, after);
+
+    Ok(())
+}
+
+#[test]
+fn test_per_attribute_scaling_curve() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 5.0, 0.0, 100.0, true));
+    let mut crit = ItemAttribute::new("crit", 1.0, 0.0, 100.0, true);
+    crit.set_curve(Some(ScalingCurve::Logarithmic));
+    assert_eq!(crit.get_curve(), Some(ScalingCurve::Logarithmic));
+    generator.set_attribute("weapon", "sword", crit);
+
+    let options = GeneratorOptions {
+        scaling_factor: 2.0,
+        ..merge_options()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let damage = items[0].get_attribute("damage").unwrap().get_initial_value();
+    let crit = items[0].get_attribute("crit").unwrap().get_initial_value();
+
+    // Level 10: damage inherits the linear option curve, crit uses its own logarithmic curve
+    assert!((damage - (5.0 + 10.0 * 2.0)).abs() < 1e-9);
+    assert!((crit - (1.0 + 2.0 * 11.0_f64.ln())).abs() < 1e-9);
+
+    Ok(())
+}
+
+#[test]
+fn test_options_validate() {
+    assert!(GeneratorOptions::default().validate().is_ok());
+
+    let zero_items = GeneratorOptions {
+        number_of_items: 0,
+        ..Default::default()
+    };
+    assert!(zero_items.validate().is_ok());
+
+    let invalid = [
+        GeneratorOptions {
+            base_level: -1.0,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            scaling_factor: -0.5,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            affix_chance: 1.5,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            affix_chance: -0.1,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            level_variance: -1.0,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            base_level: f64::NAN,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            scaling_factor: f64::NAN,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            affix_chance: f64::NAN,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            level_variance: f64::INFINITY,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            luck: f64::NAN,
+            ..Default::default()
+        },
+        GeneratorOptions {
+            power_budget: Some(f64::NAN),
+            ..Default::default()
+        },
+    ];
+    for options in &invalid {
+        assert!(matches!(options.validate(), Err(PraedaError::InvalidData(_))), "{:?}", options);
+    }
+}
+
+#[test]
+fn test_generate_loot_rejects_invalid_options() {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        base_level: -5.0,
+        ..Default::default()
+    };
+
+    let result = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test");
+    assert!(matches!(result, Err(PraedaError::InvalidData(_))));
+    assert!(generator.get_loot("test").is_empty());
+
+    // A negative variance is rejected instead of panicking on an empty level range
+    let options = GeneratorOptions {
+        base_level: 10.0,
+        level_variance: -3.0,
+        ..Default::default()
+    };
+    let result = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test");
+    assert!(matches!(result, Err(PraedaError::InvalidData(_))));
+}
+
+#[test]
+fn test_summarize_batch() -> Result<()> {
+    let item = |quality: &str, item_type: &str, subtype: &str| {
+        Item::new("thing", quality, item_type, subtype, Affix::empty(), Affix::empty(), HashMap::new())
+    };
+    let items = vec![
+        item("common", "weapon", "sword"),
+        item("common", "weapon", "axe"),
+        item("rare", "armor", "head"),
+        item("common", "weapon", "sword"),
+    ];
+
+    let qualities = PraedaGenerator::summarize_qualities(&items);
+    assert_eq!(qualities.len(), 2);
+    assert_eq!(qualities["common"], 3);
+    assert_eq!(qualities["rare"], 1);
+
+    let types = PraedaGenerator::summarize_types(&items);
+    assert_eq!(types["weapon"], 3);
+    assert_eq!(types["armor"], 1);
+
+    let subtypes = PraedaGenerator::summarize_subtypes(&items);
+    assert_eq!(subtypes["sword"], 2);
+    assert_eq!(subtypes["axe"], 1);
+    assert_eq!(subtypes["head"], 1);
+
+    // Totals line up with a generated batch
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 25,
+        ..Default::default()
+    };
+    let generated = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(PraedaGenerator::summarize_qualities(&generated).values().sum::<usize>(), 25);
+
+    Ok(())
+}
+
+#[test]
+fn test_float_quality_weights_distribution() -> Result<()> {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data_f64("common", 1.25);
+    generator.set_quality_data_f64("uncommon", 0.75);
+    generator.set_quality_data_f64("rare", 0.5);
+    generator.set_item_type("weapon", 1);
+    generator.set_item_subtype("weapon", "sword", 1);
+    assert!(generator.has_quality("rare"));
+    assert_eq!(generator.get_quality_data_f64()["uncommon"], 0.75);
+
+    let options = GeneratorOptions {
+        number_of_items: 10000,
+        affix_chance: 0.0,
+        seed: Some(3),
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let counts = PraedaGenerator::summarize_qualities(&items);
+
+    // Total weight is 2.5, so expect 50% / 30% / 20%
+    let share = |quality: &str| counts.get(quality).copied().unwrap_or(0) as f64 / items.len() as f64;
+    assert!((share("common") - 0.5).abs() < 0.03, "common {}", share("common"));
+    assert!((share("uncommon") - 0.3).abs() < 0.03, "uncommon {}", share("uncommon"));
+    assert!((share("rare") - 0.2).abs() < 0.03, "rare {}", share("rare"));
+
+    Ok(())
+}
+
+#[test]
+fn test_toml_quality_data_replaces_float_weights() -> Result<()> {
+    let toml_str = r#"
+[quality_data]
+common = 100
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+"#;
+    let options = GeneratorOptions {
+        number_of_items: 5,
+        ..Default::default()
+    };
+
+    for merge in [false, true] {
+        let mut generator = PraedaGenerator::new();
+        generator.set_quality_data_f64("mythic", 0.5);
+        if merge {
+            generator.load_data_merge(toml_str)?;
+        } else {
+            generator.load_data(toml_str)?;
+        }
+
+        assert!(generator.get_quality_data_f64().is_empty());
+        let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+        assert!(items.iter().all(|item| item.get_quality() == "common"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_mixed_quality_weights_error() {
+    let mut generator = create_test_generator();
+    generator.set_quality_data_f64("mythic", 0.5);
+
+    let result = generator.generate_loot(&GeneratorOptions::default(), &GeneratorOverrides::empty(), "test");
+    assert!(matches!(result, Err(PraedaError::InvalidData(_))));
+}
+
+#[test]
+fn test_can_generate() {
+    let mut generator = create_test_generator();
+    assert!(generator.can_generate("weapon", "sword", "rare"));
+
+    // Unknown entries are never reachable
+    assert!(!generator.can_generate("weapon", "bow", "rare"));
+    assert!(!generator.can_generate("weapon", "sword", "mythic"));
+
+    // A zero weight anywhere along the chain blocks the item
+    generator.set_quality_data("rare", 0);
+    assert!(!generator.can_generate("weapon", "sword", "rare"));
+    generator.set_quality_data("rare", 30);
+
+    generator.set_item_subtype("weapon", "sword", 0);
+    assert!(!generator.can_generate("weapon", "sword", "rare"));
+    generator.set_item_subtype("weapon", "sword", 1);
+
+    generator.set_item_type("weapon", 0);
+    assert!(!generator.can_generate("weapon", "sword", "rare"));
+}
+
+#[test]
+fn test_can_generate_requires_names_or_attributes() {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 1);
+    generator.set_item_type("trinket", 1);
+    generator.set_item_subtype("trinket", "ring", 1);
+    assert!(!generator.can_generate("trinket", "ring", "common"));
+
+    generator.set_item("trinket", "ring", vec!["band"]);
+    assert!(generator.can_generate("trinket", "ring", "common"));
+
+    // A minimum affix count with no affixes configured can never be met
+    generator.set_quality_min_affixes("common", 1);
+    assert!(!generator.can_generate("trinket", "ring", "common"));
+}
+
+#[test]
+fn test_empty_drop_weight() -> Result<()> {
+    let mut generator = create_test_generator();
+    assert_eq!(generator.get_empty_drop_weight(), 0);
+
+    // Qualities weigh 190 in total, so an empty weight of 190 makes half of all rolls empty
+    generator.set_empty_drop_weight(190);
+    assert_eq!(generator.get_empty_drop_weight(), 190);
+
+    let options = GeneratorOptions {
+        number_of_items: 4000,
+        seed: Some(11),
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let rate = 1.0 - items.len() as f64 / 4000.0;
+    assert!((rate - 0.5).abs() < 0.04, "empty drop rate {}", rate);
+    assert_eq!(generator.get_loot("test").len(), items.len());
+
+    generator.set_empty_drop_weight(0);
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(items.len(), 4000);
+
+    Ok(())
+}
+
+#[test]
+fn test_affix_count_distribution() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_prefix_attribute("", "", "sharp", ItemAttribute::new("damage", 1.0, 0.0, 5.0, true));
+    generator.set_suffix_attribute("", "", "of vigor", ItemAttribute::new("health", 1.0, 0.0, 5.0, true));
+    generator.set_affix_count_distribution(vec![(0, 50), (1, 30), (2, 15), (3, 5)]);
+    assert_eq!(generator.get_affix_count_distribution().len(), 4);
+
+    let options = GeneratorOptions {
+        number_of_items: 5000,
+        affix_chance: 1.0,
+        seed: Some(5),
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+
+    let mut by_count = [0usize; 3];
+    for item in &items {
+        let count = (!item.get_prefix().get_name().is_empty()) as usize
+            + (!item.get_suffix().get_name().is_empty()) as usize;
+        by_count[count] += 1;
+    }
+    let share = |count: usize| by_count[count] as f64 / items.len() as f64;
+
+    // Counts above two fill both slots, so 2 and 3 together make up 20%
+    assert!((share(0) - 0.5).abs() < 0.03, "0 affixes {}", share(0));
+    assert!((share(1) - 0.3).abs() < 0.03, "1 affix {}", share(1));
+    assert!((share(2) - 0.2).abs() < 0.03, "2 affixes {}", share(2));
+
+    Ok(())
+}
+
+#[test]
+fn test_simulate_drop_rates() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_quality_data("legendary", 10);
+
+    let options = GeneratorOptions {
+        seed: Some(21),
+        ..Default::default()
+    };
+    let stats = generator.simulate(&options, &GeneratorOverrides::empty(), 10000)?;
+
+    assert_eq!(stats.trials, 10000);
+    assert_eq!(stats.items_generated, 10000);
+    assert_eq!(stats.quality_counts.values().sum::<usize>(), 10000);
+    assert_eq!(stats.type_counts.values().sum::<usize>(), 10000);
+
+    // Legendary is 10 of 200 total weight
+    let rate = stats.quality_rate("legendary");
+    assert!((rate - 0.05).abs() < 0.01, "legendary rate {}", rate);
+
+    // Simulations are reproducible with a seed
+    assert_eq!(generator.simulate(&options, &GeneratorOverrides::empty(), 10000)?, stats);
+
+    Ok(())
+}
+
+#[test]
+fn test_sort_items_by_attribute() {
+    let item = |name: &str, damage: Option<f64>| {
+        let mut attributes = HashMap::new();
+        if let Some(damage) = damage {
+            attributes.insert("damage".to_string(), ItemAttribute::new("damage", damage, 0.0, 100.0, true));
+        }
+        Item::new(name, "common", "weapon", "sword", Affix::empty(), Affix::empty(), attributes)
+    };
+    let mut items = vec![
+        item("dull", Some(3.0)),
+        item("broken", None),
+        item("keen", Some(12.5)),
+        item("plain", Some(7.0)),
+    ];
+
+    assert_eq!(items[2].total_attribute("damage"), 12.5);
+    assert_eq!(items[1].total_attribute("damage"), 0.0);
+    assert_eq!(items[0].compare_by_attribute(&items[3], "damage"), std::cmp::Ordering::Less);
+
+    sort_items_by_attribute(&mut items, "damage");
+    let names: Vec<&str> = items.iter().map(|item| item.get_name()).collect();
+    assert_eq!(names, vec!["keen", "plain", "dull", "broken"]);
+}
+
+#[test]
+fn test_strict_affix_matching() -> Result<()> {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 1);
+    generator.set_item_type("armor", 1);
+    generator.set_item_subtype("armor", "chest", 1);
+    generator.set_item_type("container", 1);
+    generator.set_item_subtype("container", "chest", 1);
+
+    // A bare-subtype affix matches every type with a "chest" subtype
+    generator.set_prefix_attribute("", "chest", "sturdy", ItemAttribute::new("defense", 2.0, 0.0, 10.0, true));
+    generator.set_prefix_attribute("container", "chest", "locked", ItemAttribute::new("lock", 1.0, 0.0, 5.0, true));
+
+    let mut options = GeneratorOptions {
+        number_of_items: 200,
+        affix_chance: 1.0,
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "loose")?;
+    assert!(items.iter().any(|item| item.get_type() == "armor" && item.get_prefix().get_name() == "sturdy"));
+
+    options.strict_affix_matching = true;
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "strict")?;
+    for item in &items {
+        match item.get_type() {
+            "armor" => assert!(item.get_prefix().get_name().is_empty()),
+            _ => assert_eq!(item.get_prefix().get_name(), "locked"),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_localized_item_names() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    generator.set_item_localized("weapon", "sword", "en", vec!["broadsword"]);
+    generator.set_item_localized("weapon", "sword", "fr", vec!["épée longue"]);
+    assert_eq!(generator.get_item_names_localized("weapon", "sword", "fr"), vec!["épée longue"]);
+
+    let mut options = GeneratorOptions {
+        number_of_items: 5,
+        ..Default::default()
+    };
+    let names = |generator: &mut PraedaGenerator, options: &GeneratorOptions| -> Result<Vec<String>> {
+        let items = generator.generate_loot(options, &GeneratorOverrides::empty(), "test")?;
+        Ok(items.iter().map(|item| item.get_name().to_string()).collect())
+    };
+
+    assert!(names(&mut generator, &options)?.iter().all(|name| name == "longsword"));
+
+    options.locale = Some("en".to_string());
+    assert!(names(&mut generator, &options)?.iter().all(|name| name == "broadsword"));
+
+    options.locale = Some("fr".to_string());
+    assert!(names(&mut generator, &options)?.iter().all(|name| name == "épée longue"));
+
+    // Locales without names fall back to the default list
+    options.locale = Some("de".to_string());
+    assert!(names(&mut generator, &options)?.iter().all(|name| name == "longsword"));
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_templates() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item_subtype("weapon", "axe", 1);
+    generator.define_attribute_template(
+        "weapon_base",
+        vec![
+            ItemAttribute::new("damage", 10.0, 5.0, 20.0, true),
+            ItemAttribute::new("attack_speed", 1.0, 0.5, 2.0, true),
+        ],
+    );
+    assert_eq!(generator.get_attribute_template("weapon_base").unwrap().len(), 2);
+
+    generator.apply_attribute_template("weapon", "sword", "weapon_base")?;
+    generator.apply_attribute_template("weapon", "axe", "weapon_base")?;
+
+    for subtype in ["sword", "axe"] {
+        assert!(generator.has_attribute("weapon", subtype, "damage"));
+        assert!(generator.has_attribute("weapon", subtype, "attack_speed"));
+    }
+
+    let items = generator.generate_loot(
+        &merge_options(),
+        &GeneratorOverrides::new("", "weapon", "axe"),
+        "test",
+    )?;
+    assert!(items[0].has_attribute("attack_speed"));
+
+    let missing = generator.apply_attribute_template("weapon", "sword", "armor_base");
+    assert!(matches!(missing, Err(PraedaError::InvalidData(_))));
+
+    Ok(())
+}
+
+#[test]
+fn test_attributes_ordered() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    for name in ["speed", "armor", "damage", "luck"] {
+        generator.set_attribute("weapon", "sword", ItemAttribute::new(name, 1.0, 0.0, 10.0, true));
+    }
+
+    let options = GeneratorOptions {
+        number_of_items: 2,
+        seed: Some(4),
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+
+    let names = |item: &Item| -> Vec<String> {
+        item.get_attributes_ordered()
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect()
+    };
+    assert_eq!(names(&items[0]), vec!["armor", "damage", "level", "luck", "speed"]);
+    assert_eq!(names(&items[0]), names(&items[1]));
+
+    Ok(())
+}
+
+#[test]
+fn test_item_display() -> Result<()> {
+    let mut item = Item::new(
+        "Longsword",
+        "rare",
+        "weapon",
+        "sword",
+        Affix::new("Flaming", Vec::new()),
+        Affix::new("of Fire", Vec::new()),
+        HashMap::new(),
+    );
+    item.set_attribute("level", ItemAttribute::new("level", 12.0, 0.0, 0.0, false));
+    item.set_attribute("damage", ItemAttribute::new("damage", 18.0, 0.0, 0.0, true));
+    assert_eq!(item.display_name(), "Flaming Longsword of Fire");
+    assert_eq!(item.to_string(), "Flaming Longsword of Fire [rare weapon/sword] lvl 12 {damage: 18}");
+
+    let mut generator = create_test_generator();
+    let items = generator.generate_loot(&GeneratorOptions::default(), &GeneratorOverrides::new("common", "weapon", ""), "test")?;
+    let rendered = items[0].to_string();
+    assert!(rendered.contains("[common weapon/"));
+    assert!(rendered.contains("damage: "));
+
+    // A renamed level attribute is still shown as the level, not as a plain attribute
+    let options = GeneratorOptions {
+        level_attribute_name: "ilvl".to_string(),
+        ..merge_options()
+    };
+    let items = create_attribute_merge_generator()
+        .generate_loot_shared(&options, &GeneratorOverrides::empty())?;
+    assert_eq!(items[0].get_level_attribute(), "ilvl");
+    let rendered = items[0].to_string();
+    assert!(rendered.contains("] lvl 10 {"), "{}", rendered);
+    assert!(!rendered.contains("ilvl"), "{}", rendered);
+
+    Ok(())
+}
+
+#[test]
+fn test_affix_budget_limits_total_affixes() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_prefix_attribute("weapon", "", "Sharp", ItemAttribute::new("damage", 2.0, 0.0, 10.0, true));
+    generator.set_suffix_attribute("weapon", "", "of the Bear", ItemAttribute::new("strength", 2.0, 0.0, 10.0, true));
+
+    let options = GeneratorOptions {
+        number_of_items: 200,
+        affix_budget: Some(1),
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let has_prefix = |item: &Item| !item.get_prefix().get_name().is_empty();
+    let has_suffix = |item: &Item| !item.get_suffix().get_name().is_empty();
+    assert!(items.iter().all(|item| has_prefix(item) != has_suffix(item)));
+    assert!(items.iter().any(has_prefix));
+    assert!(items.iter().any(has_suffix));
+
+    let options = GeneratorOptions {
+        affix_budget: Some(0),
+        ..options
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert!(items.iter().all(|item| !has_prefix(item) && !has_suffix(item)));
+
+    Ok(())
+}
+
+#[test]
+fn test_affix_min_level() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_suffix_attribute("weapon", "", "of the Bear", ItemAttribute::new("strength", 2.0, 0.0, 10.0, true));
+    generator.set_affix_attribute_with_min_level(
+        "weapon",
+        "",
+        false,
+        "of the Dragon",
+        ItemAttribute::new("fire_damage", 25.0, 0.0, 50.0, true),
+        40.0,
+    );
+    assert_eq!(generator.get_suffixes("weapon", "")[1].get_min_level(), 40.0);
+
+    let low = GeneratorOptions {
+        number_of_items: 200,
+        base_level: 10.0,
+        level_variance: 5.0,
+        affix_chance: 1.0,
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&low, &GeneratorOverrides::empty(), "low")?;
+    assert!(items.iter().all(|item| item.get_suffix().get_name() == "of the Bear"));
+
+    let high = GeneratorOptions {
+        base_level: 50.0,
+        ..low
+    };
+    let items = generator.generate_loot(&high, &GeneratorOverrides::empty(), "high")?;
+    assert!(items.iter().any(|item| item.get_suffix().get_name() == "of the Dragon"));
+
+    Ok(())
+}
+
+#[test]
+fn test_affix_tier_gated_by_quality() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_suffix_attribute("weapon", "", "of the Gods", ItemAttribute::new("strength", 20.0, 0.0, 50.0, true));
+    generator.set_affix_tier("weapon", "", false, "of the Gods", 2);
+    generator.set_quality_affix_tier_min("rare", 2);
+    let gods = generator.get_suffixes("weapon", "").into_iter().find(|a| a.get_name() == "of the Gods");
+    assert_eq!(gods.map(|a| a.get_tier()), Some(2));
+    assert_eq!(generator.get_quality_affix_tier("common"), 0);
+
+    let options = GeneratorOptions {
+        number_of_items: 200,
+        base_level: 10.0,
+        affix_chance: 1.0,
+        ..Default::default()
+    };
+    let common = GeneratorOverrides::new("common", "weapon", "");
+    let items = generator.generate_loot(&options, &common, "common")?;
+    assert!(items.iter().all(|item| item.get_suffix().get_name() != "of the Gods"));
+
+    let rare = GeneratorOverrides::new("rare", "weapon", "");
+    let items = generator.generate_loot(&options, &rare, "rare")?;
+    assert!(items.iter().any(|item| item.get_suffix().get_name() == "of the Gods"));
+
+    Ok(())
+}
+
+#[test]
+fn test_level_weighted_affixes_track_item_level() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    for min_level in (0..100).step_by(10) {
+        generator.set_affix_attribute_with_min_level(
+            "weapon",
+            "",
+            false,
+            &format!("of Tier {}", min_level),
+            ItemAttribute::new("power", min_level as f64, 0.0, 100.0, true),
+            min_level as f64,
+        );
+    }
+    let suffixes = generator.get_suffixes("weapon", "");
+
+    let mut previous_mean = -1.0;
+    for level in [10.0, 30.0, 50.0, 70.0, 90.0] {
+        let options = GeneratorOptions {
+            number_of_items: 300,
+            base_level: level,
+            level_weighted_affixes: true,
+            ..merge_options()
+        };
+        let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+        let total: f64 = items
+            .iter()
+            .map(|item| {
+                let name = item.get_suffix().get_name();
+                suffixes.iter().find(|a| a.get_name() == name).unwrap().get_min_level()
+            })
+            .sum();
+        let mean = total / items.len() as f64;
+
+        // Uniform selection would average level / 2; weighting keeps affixes near the level
+        assert!(level - mean < 25.0, "level {} averaged min_level {}", level, mean);
+        assert!(mean > previous_mean);
+        previous_mean = mean;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_flat_item_attributes_from_toml() -> Result<()> {
+    let header = r#"
+[quality_data]
+common = 1
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+"#;
+    let nested = r#"
+[[item_attributes]]
+item_type = "weapon"
+subtype = ""
+[[item_attributes.attributes]]
+name = "durability"
+initial_value = 50.0
+min = 0.0
+max = 100.0
+required = true
+
+[[item_attributes]]
+item_type = "weapon"
+subtype = "sword"
+[[item_attributes.attributes]]
+name = "damage"
+initial_value = 10.0
+min = 1.0
+max = 20.0
+required = true
+[[item_attributes.attributes]]
+name = "crit"
+initial_value = 0.05
+min = 0.0
+max = 1.0
+required = false
+"#;
+    let flat = r#"
+[item_attributes]
+weapon."" = [
+    { name = "durability", initial_value = 50.0, min = 0.0, max = 100.0, required = true },
+]
+weapon.sword = [
+    { name = "damage", initial_value = 10.0, min = 1.0, max = 20.0, required = true },
+    { name = "crit", initial_value = 0.05, min = 0.0, max = 1.0, required = false },
+]
+"#;
+
+    let mut nested_generator = PraedaGenerator::new();
+    nested_generator.load_data(&format!("{}{}", header, nested))?;
+    let mut flat_generator = PraedaGenerator::new();
+    flat_generator.load_data(&format!("{}{}", header, flat))?;
+
+    let flat_attributes = flat_generator.get_config_snapshot().item_attributes;
+    assert_eq!(flat_attributes.len(), 2);
+    assert_eq!(flat_attributes, nested_generator.get_config_snapshot().item_attributes);
+
+    Ok(())
+}
+
+#[test]
+fn test_malformed_item_attributes_report_the_field() {
+    let header = r#"
+[quality_data]
+common = 1
+"#;
+    let nested = r#"
+[[item_attributes]]
+item_type = "weapon"
+subtype = "sword"
+[[item_attributes.attributes]]
+name = "damage"
+initial_value = "ten"
+min = 1.0
+max = 20.0
+required = true
+"#;
+    let flat = r#"
+[item_attributes]
+weapon.sword = [{ name = "damage", initial_value = "ten", min = 1.0, max = 20.0, required = true }]
+"#;
+
+    for section in [nested, flat] {
+        let mut generator = PraedaGenerator::new();
+        let message = generator.load_data(&format!("{}{}", header, section)).unwrap_err().to_string();
+        assert!(message.contains("invalid type"), "{}", message);
+        assert!(!message.contains("did not match any variant"), "{}", message);
+    }
+}
+
+#[test]
+fn test_affix_min_level_from_toml() -> Result<()> {
+    let toml_str = r#"
+[quality_data]
+common = 1
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+
+[[item_affixes]]
+item_type = "weapon"
+subtype = "sword"
+[[item_affixes.suffixes]]
+name = "of the Dragon"
+min_level = 40.0
+[[item_affixes.suffixes.attributes]]
+name = "fire_damage"
+initial_value = 25.0
+min = 0.0
+max = 50.0
+required = true
+    "#;
+
+    let mut generator = PraedaGenerator::new();
+    generator.load_data(toml_str)?;
+    assert_eq!(generator.get_suffixes("weapon", "sword")[0].get_min_level(), 40.0);
+
+    let options = GeneratorOptions {
+        number_of_items: 50,
+        base_level: 5.0,
+        affix_chance: 1.0,
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert!(items.iter().all(|item| item.get_suffix().get_name().is_empty()));
+
+    Ok(())
+}
+
+#[test]
+fn test_unregistered_type_override_does_not_produce_item() {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 1);
+
+    let result = generator.generate_loot(
+        &GeneratorOptions::default(),
+        &GeneratorOverrides::new("", "relic", ""),
+        "test",
+    );
+    assert!(matches!(result, Err(PraedaError::MissingItemType(t)) if t == "relic"));
+    assert!(generator.get_loot("test").is_empty());
+}
+
+#[test]
+fn test_duplicate_affix_attributes_last_wins() -> Result<()> {
+    let mut affix = Affix::new(
+        "sharp",
+        vec![
+            ItemAttribute::new("damage", 5.0, 0.0, 0.0, true),
+            ItemAttribute::new("speed", 1.0, 0.0, 0.0, true),
+            ItemAttribute::new("damage", 8.0, 0.0, 0.0, true),
+        ],
+    );
+    affix.dedup_attributes();
+    assert_eq!(affix.get_attributes().len(), 2);
+    assert_eq!(affix.get_attributes()[0].get_initial_value(), 8.0);
+
+    let toml_str = r#"
+[quality_data]
+common = 1
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+
+[[item_affixes]]
+item_type = "weapon"
+subtype = "sword"
+[[item_affixes.prefixes]]
+name = "sharp"
+[[item_affixes.prefixes.attributes]]
+name = "damage"
+initial_value = 5.0
+min = 0.0
+max = 0.0
+required = true
+[[item_affixes.prefixes.attributes]]
+name = "damage"
+initial_value = 8.0
+min = 0.0
+max = 0.0
+required = true
+    "#;
+
+    let mut generator = PraedaGenerator::new();
+    generator.load_data(toml_str)?;
+
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+    let item = &items[0];
+    assert_eq!(item.get_prefix().get_name(), "sharp");
+    assert_eq!(item.get_prefix().get_attributes().len(), 1);
+    assert_eq!(item.get_attribute("damage").unwrap().get_initial_value(), 8.0);
+    assert_eq!(item.get_attribute_breakdown("damage").unwrap().len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_loot_shared_across_threads() {
+    use std::sync::Arc;
+    use std::thread;
+
+    fn assert_sync<T: Sync + Send>() {}
+    assert_sync::<PraedaGenerator>();
+
+    let generator = Arc::new(create_test_generator());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let generator = Arc::clone(&generator);
+            thread::spawn(move || {
+                let options = GeneratorOptions {
+                    number_of_items: 10,
+                    ..Default::default()
+                };
+                generator
+                    .generate_loot_shared(&options, &GeneratorOverrides::empty())
+                    .unwrap()
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        let items = handle.join().unwrap();
+        assert_eq!(items.len(), 10);
+    }
+}
+
+#[test]
+fn test_affix_metadata_is_merged_into_item() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    generator.set_affix_metadata("weapon", "sword", true, "Soulbound", "soulbound", serde_json::json!(true));
+    generator.set_subtype_metadata("weapon", "sword", "soulbound", serde_json::json!(false));
+
+    let prefixes = generator.get_prefixes("weapon", "sword");
+    assert_eq!(prefixes.len(), 1);
+    assert!(prefixes[0].get_attributes().is_empty());
+    assert_eq!(prefixes[0].get_metadata("soulbound"), Some(&serde_json::json!(true)));
+
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(items[0].get_prefix().get_name(), "Soulbound");
+    assert_eq!(items[0].get_metadata("soulbound"), Some(&serde_json::json!(true)));
+
+    Ok(())
+}
+
+#[test]
+fn test_affix_description_is_merged_into_item() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    generator.set_affix_description("weapon", "sword", true, "Ancient", "Forged before the first age.");
+    generator.set_affix_description("weapon", "sword", false, "of Embers", "Warm to the touch.");
+
+    let suffix: Affix = serde_json::from_str(r#"{"name": "of Ash", "attributes": []}"#)?;
+    assert_eq!(suffix.get_description(), "");
+
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(items[0].get_prefix().get_name(), "Ancient");
+    assert_eq!(items[0].get_suffix().get_name(), "of Embers");
+    assert_eq!(
+        items[0].get_description(),
+        "Forged before the first age. Warm to the touch."
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_power_budget_skips_expensive_affixes() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    generator.set_prefix_attribute("weapon", "sword", "Keen", ItemAttribute::new("damage", 2.0, 0.0, 5.0, true));
+    generator.set_prefix_attribute("weapon", "sword", "Mighty", ItemAttribute::new("damage", 40.0, 0.0, 50.0, true));
+    generator.set_suffix_attribute("weapon", "sword", "of Giants", ItemAttribute::new("strength", 30.0, 0.0, 50.0, true));
+    generator.set_suffix_attribute("weapon", "sword", "of Sparks", ItemAttribute::new("damage", 1.0, 0.0, 5.0, true));
+    generator.set_affix_power_cost("weapon", "sword", false, "of Sparks", 4.0);
+    assert_eq!(generator.get_prefixes("weapon", "sword")[1].get_power_cost(), 40.0);
+
+    let options = GeneratorOptions {
+        number_of_items: 50,
+        seed: Some(3),
+        power_budget: Some(5.0),
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    for item in &items {
+        // Keen (2) leaves 3, which is too little for of Sparks (4) or of Giants (30)
+        assert_eq!(item.get_prefix().get_name(), "Keen");
+        assert!(item.get_suffix().get_name().is_empty());
+    }
+
+    let unbudgeted = GeneratorOptions { seed: Some(3), number_of_items: 50, ..merge_options() };
+    let items = generator.generate_loot(&unbudgeted, &GeneratorOverrides::empty(), "test")?;
+    assert!(items.iter().any(|item| item.get_prefix().get_name() == "Mighty"));
+
+    Ok(())
+}
+
+#[test]
+fn test_seeded_affix_range_rolls_repeat() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    let mut fire = ItemAttribute::new("fire", 0.0, 1.0, 1000.0, true);
+    fire.set_roll_range(true);
+    generator.set_prefix_attribute("weapon", "sword", "Volatile", fire);
+
+    let mut rolls = |seed: u64| -> Result<Vec<f64>> {
+        let options = GeneratorOptions { number_of_items: 20, seed: Some(seed), ..merge_options() };
+        let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+        Ok(items
+            .iter()
+            .map(|item| item.get_attribute("fire").unwrap().get_initial_value())
+            .collect())
+    };
+
+    let first = rolls(8)?;
+    assert!(first.iter().all(|value| (1.0..=1000.0).contains(value)));
+    assert_eq!(first, rolls(8)?);
+    assert_ne!(first, rolls(9)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_group_picks_exact_count() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    let names = ["fire", "frost", "shock", "poison", "holy"];
+    let group: Vec<ItemAttribute> = names
+        .iter()
+        .map(|name| ItemAttribute::new(name, 5.0, 0.0, 100.0, false))
+        .collect();
+    generator.set_attribute_group("weapon", "sword", "elements", group, 2);
+    assert_eq!(generator.get_attribute_groups("weapon", "sword")[0].get_pick(), 2);
+
+    let options = GeneratorOptions { number_of_items: 40, seed: Some(17), affix_chance: 0.0, ..merge_options() };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let mut seen = HashMap::new();
+    for item in &items {
+        let rolled: Vec<&str> = names.iter().copied().filter(|name| item.has_attribute(name)).collect();
+        assert_eq!(rolled.len(), 2);
+        for name in rolled {
+            *seen.entry(name).or_insert(0) += 1;
+        }
+    }
+    assert_eq!(seen.len(), names.len());
+
+    Ok(())
+}
+
+#[test]
+fn test_set_attribute_adds_and_replace_attribute_overwrites() -> Result<()> {
+    let damage_of = |configure: &dyn Fn(&mut PraedaGenerator)| -> Result<f64> {
+        let mut generator = create_attribute_merge_generator();
+        configure(&mut generator);
+        let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+        Ok(items[0].get_attribute("damage").unwrap().get_initial_value())
+    };
+
+    let additive = damage_of(&|g| {
+        g.set_attribute("weapon", "sword", ItemAttribute::new("damage", 10.0, 0.0, 1000.0, true));
+        g.set_attribute("weapon", "sword", ItemAttribute::new("damage", 5.0, 0.0, 1000.0, true));
+    })?;
+    let single_sum = damage_of(&|g| {
+        g.set_attribute("weapon", "sword", ItemAttribute::new("damage", 15.0, 0.0, 1000.0, true));
+    })?;
+    assert_eq!(additive, single_sum);
+
+    let replaced = damage_of(&|g| {
+        g.set_attribute("weapon", "sword", ItemAttribute::new("damage", 10.0, 0.0, 1000.0, true));
+        g.replace_attribute("weapon", "sword", ItemAttribute::new("damage", 5.0, 0.0, 1000.0, true));
+    })?;
+    let single_replacement = damage_of(&|g| {
+        g.set_attribute("weapon", "sword", ItemAttribute::new("damage", 5.0, 0.0, 1000.0, true));
+    })?;
+    assert_eq!(replaced, single_replacement);
+    assert!(replaced < additive);
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_bounds_grow_with_level() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    let mut damage = ItemAttribute::new("damage", 0.0, 0.0, 5.0, true);
+    damage.set_max_per_level(5.0);
+    generator.set_attribute("weapon", "sword", damage);
+
+    let damage_at = |generator: &mut PraedaGenerator, level: f64| -> Result<ItemAttribute> {
+        let options = GeneratorOptions {
+            base_level: level,
+            scaling_factor: 10.0,
+            ..merge_options()
+        };
+        let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+        Ok(items[0].get_attribute("damage").unwrap().clone())
+    };
+
+    // 10 per level scaled, capped at 5 + 5 per level
+    let low = damage_at(&mut generator, 2.0)?;
+    assert_eq!(low.get_initial_value(), 15.0);
+    assert_eq!(low.get_max(), 15.0);
+
+    let high = damage_at(&mut generator, 10.0)?;
+    assert_eq!(high.get_initial_value(), 55.0);
+    assert_eq!(high.get_max(), 55.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_loot_scaled_overrides_scaling() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 10.0, 0.0, 0.0, true));
+    let base = GeneratorOptions {
+        curve: Some(ScalingCurve::Logarithmic),
+        ..merge_options()
+    };
+
+    let damage = |items: Vec<Item>| items[0].total_attribute("damage");
+    // Level 10: 10 + 10 * 2 linearly, 10 * 2^10 exponentially
+    assert_eq!(damage(generator.generate_loot_scaled(&base, true, 2.0, "linear")?), 30.0);
+    assert_eq!(damage(generator.generate_loot_scaled(&base, false, 2.0, "exponential")?), 10240.0);
+    assert_eq!(generator.get_loot("linear").len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_verify_attribute_bounds() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 10.0, 0.0, 20.0, true));
+
+    // Level 30 scales damage to 40, past the flat max of 20
+    let options = GeneratorOptions {
+        number_of_items: 3,
+        base_level: 30.0,
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let violations = generator.verify_attribute_bounds(&items);
+    assert_eq!(violations.len(), 3);
+    assert_eq!(violations[0], ("longsword".to_string(), "damage".to_string(), 40.0));
+
+    // A cap that grows with level clamps the value and keeps it within the configured bounds
+    let mut damage = ItemAttribute::new("damage", 10.0, 0.0, 20.0, true);
+    damage.set_max_per_level(0.5);
+    generator.replace_attribute("weapon", "sword", damage);
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(items[0].total_attribute("damage"), 35.0);
+    assert!(generator.verify_attribute_bounds(&items).is_empty());
+
+    // Per-level bounds follow a renamed level attribute
+    let options = GeneratorOptions {
+        level_attribute_name: "ilvl".to_string(),
+        ..options
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(items[0].total_attribute("damage"), 35.0);
+    assert!(generator.verify_attribute_bounds(&items).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_set_attribute_override_replaces_inherited() -> Result<()> {
+    let speed_of = |override_sword: bool| -> Result<(f64, f64)> {
+        let mut generator = create_attribute_merge_generator();
+        generator.set_item_subtype("weapon", "axe", 1);
+        generator.set_attribute("weapon", "", ItemAttribute::new("speed", 1.0, 0.0, 0.0, false));
+        let sword_speed = ItemAttribute::new("speed", 2.0, 0.0, 0.0, false);
+        if override_sword {
+            generator.set_attribute_override("weapon", "sword", sword_speed);
+            assert!(generator.is_attribute_override("weapon", "sword", "speed"));
+        } else {
+            generator.set_attribute("weapon", "sword", sword_speed);
+        }
+
+        let options = GeneratorOptions {
+            scaling_factor: 0.0,
+            ..merge_options()
+        };
+        let mut speed = |subtype: &str| -> Result<f64> {
+            let overrides = GeneratorOverrides::new("", "weapon", subtype);
+            let items = generator.generate_loot(&options, &overrides, "test")?;
+            Ok(items[0].get_attribute("speed").unwrap().get_initial_value())
+        };
+        Ok((speed("sword")?, speed("axe")?))
+    };
+
+    // Inherited and subtype attributes stack by default
+    assert_eq!(speed_of(false)?, (3.0, 1.0));
+    // The override replaces the inherited value for swords only
+    assert_eq!(speed_of(true)?, (2.0, 1.0));
+
+    Ok(())
+}
+
+#[test]
+fn test_get_subtype_weights() {
+    let mut generator = PraedaGenerator::new();
+    generator.set_item_type("weapon", 1);
+    generator.set_item_subtype("weapon", "sword", 40);
+    generator.set_item_subtype("weapon", "axe", 25);
+
+    let weights = generator.get_subtype_weights("weapon");
+    assert_eq!(weights.len(), 2);
+    assert_eq!(weights.get("sword"), Some(&40));
+    assert_eq!(weights.get("axe"), Some(&25));
+
+    assert!(generator.get_subtype_weights("armor").is_empty());
+}
+
+#[test]
+fn test_max_level_caps_generated_level() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 50,
+        base_level: 60.0,
+        level_variance: 10.0,
+        max_level: Some(12.0),
+        ..Default::default()
+    };
+
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "capped")?;
+    assert_eq!(items.len(), 50);
+    for item in &items {
+        let level = item.get_attribute("level").unwrap().get_initial_value();
+        assert!(level <= 12.0, "level {} exceeds the cap", level);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_unique_names_within_batch() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    let names = vec!["longsword", "shortsword", "greatsword", "rapier", "sabre"];
+    generator.set_item("weapon", "sword", names.clone());
+
+    let options = GeneratorOptions {
+        number_of_items: names.len() as u32,
+        unique_names_within_batch: true,
+        ..merge_options()
+    };
+
+    for _ in 0..10 {
+        let items = generator.generate_loot_shared(&options, &GeneratorOverrides::empty())?;
+        let mut generated: Vec<&str> = items.iter().map(|item| item.get_name()).collect();
+        generated.sort();
+        let mut expected = names.clone();
+        expected.sort();
+        assert_eq!(generated, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_format_value() {
+    let mut attribute = ItemAttribute::new("crit", 15.0, 0.0, 100.0, true);
+    assert_eq!(attribute.get_format(), AttributeFormat::Flat);
+    assert_eq!(attribute.format_value(), "15");
+
+    attribute.set_format(AttributeFormat::Percent);
+    assert_eq!(attribute.format_value(), "15%");
+
+    attribute.set_format(AttributeFormat::PerSecond);
+    assert_eq!(attribute.format_value(), "15/s");
+
+    attribute.set_format(AttributeFormat::Duration);
+    assert_eq!(attribute.format_value(), "15s");
+
+    attribute.set_format(AttributeFormat::Flat);
+    attribute.set_initial_value(12.345);
+    assert_eq!(attribute.format_value(), "12.35");
+}
+
+#[test]
+fn test_attribute_format_from_toml() -> Result<()> {
+    let toml_str = r#"
+[quality_data]
+common = 1
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+
+[[item_attributes]]
+item_type = "weapon"
+subtype = ""
+[[item_attributes.attributes]]
+name = "crit"
+initial_value = 5.0
+min = 0.0
+max = 100.0
+required = true
+format = "Percent"
+
+[[item_attributes.attributes]]
+name = "damage"
+initial_value = 5.0
+min = 0.0
+max = 100.0
+required = true
+    "#;
+
+    let mut generator = PraedaGenerator::new();
+    generator.load_data(toml_str)?;
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+    assert_eq!(items[0].get_attribute("crit").unwrap().get_format(), AttributeFormat::Percent);
+    assert_eq!(items[0].get_attribute("damage").unwrap().get_format(), AttributeFormat::Flat);
+
+    Ok(())
+}
+
+#[test]
+fn test_max_items_cap() -> Result<()> {
+    let mut generator = create_test_generator();
+    assert_eq!(generator.get_max_items(), DEFAULT_MAX_ITEMS);
+    generator.set_max_items(5);
+
+    let options = GeneratorOptions {
+        number_of_items: 5,
+        ..Default::default()
+    };
+    assert_eq!(generator.generate_loot(&options, &GeneratorOverrides::empty(), "ok")?.len(), 5);
+
+    let options = GeneratorOptions {
+        number_of_items: 6,
+        ..Default::default()
+    };
+    let result = generator.generate_loot(&options, &GeneratorOverrides::empty(), "too_many");
+    assert!(matches!(result, Err(PraedaError::InvalidData(_))));
+    assert!(generator.get_loot("too_many").is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_seeded_affixes_independent_of_insertion_order() -> Result<()> {
+    let prefixes = ["Flaming", "Frozen", "Sharp", "Heavy"];
+    let suffixes = ["of Speed", "of Might", "of the Bear"];
+
+    let build = |reverse: bool| {
+        let mut generator = create_attribute_merge_generator();
+        generator.set_item("weapon", "sword", vec!["longsword"]);
+        let mut prefix_order: Vec<&str> = prefixes.to_vec();
+        let mut suffix_order: Vec<&str> = suffixes.to_vec();
+        if reverse {
+            prefix_order.reverse();
+            suffix_order.reverse();
+        }
+        for name in prefix_order {
+            generator.set_prefix_attribute("weapon", "sword", name, ItemAttribute::new("damage", 1.0, 0.0, 100.0, true));
+        }
+        for name in suffix_order {
+            generator.set_suffix_attribute("weapon", "", name, ItemAttribute::new("speed", 1.0, 0.0, 100.0, true));
+        }
+        generator
+    };
+
+    let options = GeneratorOptions {
+        number_of_items: 20,
+        affix_chance: 0.5,
+        seed: Some(2024),
+        ..merge_options()
+    };
+    let forward = build(false).generate_loot_shared(&options, &GeneratorOverrides::empty())?;
+    let reversed = build(true).generate_loot_shared(&options, &GeneratorOverrides::empty())?;
+    assert_eq!(forward, reversed);
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_loot_lenient_keeps_successful_items() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item_subtype("weapon", "axe", 1);
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    generator.set_item("weapon", "axe", vec!["handaxe"]);
+    // Only swords have an affix, so axes cannot meet the quality's minimum and fail
+    generator.set_prefix_attribute("weapon", "sword", "Sharp", ItemAttribute::new("damage", 1.0, 0.0, 100.0, true));
+    generator.set_quality_min_affixes("common", 1);
+
+    let options = GeneratorOptions {
+        number_of_items: 40,
+        seed: Some(11),
+        ..merge_options()
+    };
+    assert!(generator.generate_loot(&options, &GeneratorOverrides::empty(), "strict").is_err());
+
+    let (items, errors) = generator.generate_loot_lenient(&options, &GeneratorOverrides::empty(), "lenient");
+    assert!(!items.is_empty());
+    assert!(!errors.is_empty());
+    assert_eq!(items.len() + errors.len(), 40);
+    assert!(items.iter().all(|item| item.get_subtype() == "sword"));
+    assert!(errors.iter().all(|e| matches!(e, PraedaError::InvalidData(_))));
+    assert_eq!(generator.get_loot("lenient"), items);
+
+    let invalid = GeneratorOptions {
+        affix_chance: 2.0,
+        ..options
+    };
+    let (items, errors) = generator.generate_loot_lenient(&invalid, &GeneratorOverrides::empty(), "invalid");
+    assert!(items.is_empty());
+    assert_eq!(errors.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_has_item_name() {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword", "shortsword"]);
+    generator.set_item_for_quality("weapon", "sword", "common", vec!["rusty sword"]);
+
+    assert!(generator.has_item_name("weapon", "sword", "longsword"));
+    assert!(generator.has_item_name("weapon", "sword", "shortsword"));
+    assert!(!generator.has_item_name("weapon", "sword", "greatsword"));
+    assert!(!generator.has_item_name("weapon", "sword", "rusty sword"));
+    assert!(!generator.has_item_name("weapon", "axe", "longsword"));
+}
+
+#[test]
+fn test_multiplicative_affix_applies_after_additive() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword"]);
+    // Base damage 10 at level 10 with a scaling factor of 0 stays at 10
+    let mut damage = ItemAttribute::new("damage", 10.0, 0.0, 1000.0, true);
+    damage.set_scaling_factor(0.0);
+    generator.set_attribute("weapon", "sword", damage);
+
+    let mut percent = ItemAttribute::new("damage", 1.2, 0.0, 0.0, true);
+    percent.set_apply_mode(ApplyMode::Multiply);
+    generator.set_prefix_attribute("weapon", "sword", "Brutal", percent);
+    generator.set_suffix_attribute("weapon", "sword", "of Force", ItemAttribute::new("damage", 5.0, 0.0, 0.0, true));
+
+    let options = GeneratorOptions {
+        scaling_factor: 0.0,
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "test")?;
+    let item = &items[0];
+    assert_eq!(item.get_prefix().get_name(), "Brutal");
+    assert_eq!(item.get_suffix().get_name(), "of Force");
+
+    // (10 + 5) * 1.2, even though the multiplying prefix comes before the adding suffix
+    let damage = item.get_attribute("damage").unwrap().get_initial_value();
+    assert!((damage - 18.0).abs() < 1e-9, "damage was {}", damage);
+    let breakdown = item.get_attribute_breakdown("damage").unwrap();
+    let brutal = breakdown.iter().find(|(source, _)| source == "Brutal").unwrap().1;
+    assert!((brutal - 3.0).abs() < 1e-9);
+
+    Ok(())
+}
+
+#[test]
+fn test_peek_loot_borrows_stored_items() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 5,
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "stored")?;
+
+    let peeked = generator.peek_loot("stored").unwrap();
+    assert_eq!(peeked, items.as_slice());
+    assert_eq!(peeked, generator.get_loot("stored").as_slice());
+    assert!(generator.peek_loot("missing").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_set_store_loot_disables_storage() -> Result<()> {
+    let mut generator = create_test_generator();
+    assert!(generator.get_store_loot());
+    generator.set_store_loot(false);
+
+    let options = GeneratorOptions {
+        number_of_items: 5,
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "unstored")?;
+    assert_eq!(items.len(), 5);
+    assert!(generator.get_loot("unstored").is_empty());
+    assert!(generator.peek_loot("unstored").is_none());
+
+    let (items, errors) = generator.generate_loot_lenient(&options, &GeneratorOverrides::empty(), "lenient");
+    assert_eq!((items.len(), errors.len()), (5, 0));
+    assert!(generator.get_loot("lenient").is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_to_json_grouped() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 10.0, 0.0, 1000.0, true));
+    generator.set_prefix_attribute("weapon", "sword", "Sharp", ItemAttribute::new("damage", 5.0, 0.0, 0.0, true));
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+
+    let json = to_json_grouped(&items)?;
+    let value: serde_json::Value = serde_json::from_str(&json)?;
+    let item = &value[0];
+    assert_eq!(item["prefix"], "Sharp");
+    assert_eq!(item["base_attributes"]["damage"], 20.0);
+    assert_eq!(item["affix_attributes"]["Sharp"]["damage"], 5.0);
+    assert_eq!(item["attributes"]["damage"], 25.0);
+    assert!(item["affix_attributes"]["Sharp"].get("level").is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_grouped_item_separates_clamping_from_affixes() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 10.0, 0.0, 22.0, true));
+    // Affixes named like the old reserved sources stay ordinary affixes
+    generator.set_prefix_attribute("weapon", "sword", "clamp", ItemAttribute::new("damage", 5.0, 0.0, 0.0, true));
+    generator.set_suffix_attribute("weapon", "sword", "base", ItemAttribute::new("armor", 3.0, 0.0, 0.0, true));
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+
+    let grouped = GroupedItem::from(&items[0]);
+    assert_eq!(grouped.base_attributes["damage"], 20.0);
+    assert_eq!(grouped.affix_attributes["clamp"]["damage"], 5.0);
+    assert_eq!(grouped.affix_attributes["base"]["armor"], 3.0);
+    assert_eq!(grouped.clamp_adjustments["damage"], -3.0);
+    assert_eq!(grouped.clamp_adjustments.len(), 1);
+    assert!(!grouped.base_attributes.contains_key("armor"));
+    assert_eq!(grouped.attributes["damage"], 22.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_to_json_camel() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("attack_speed", 1.0, 0.0, 10.0, true));
+    generator.set_prefix_attribute("weapon", "sword", "Quick", ItemAttribute::new("attack_speed", 0.5, 0.0, 0.0, true));
+    generator.set_subtype_metadata("weapon", "sword", "two_handed", serde_json::json!(false));
+    let items = generator.generate_loot(&merge_options(), &GeneratorOverrides::empty(), "test")?;
+
+    let json = to_json_camel(&items)?;
+    let value: serde_json::Value = serde_json::from_str(&json)?;
+    let item = &value[0];
+    assert_eq!(item["itemType"], "weapon");
+    assert!(item.get("type").is_none());
+    assert!(item.get("attributeSources").is_some());
+    assert_eq!(item["metadata"]["two_handed"], false);
+
+    let attack_speed = &item["attributes"]["attack_speed"];
+    assert!(attack_speed.get("initialValue").is_some());
+    assert!(attack_speed.get("initial_value").is_none());
+    assert!(item["prefix"].get("minLevel").is_some());
+    assert!(item["prefix"]["attributes"][0].get("scalingFactor").is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_quality_rank_ordering() {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("common", 100);
+    generator.set_quality_data("legendary", 50);
+    generator.set_quality_data("rare", 5);
+    generator.set_quality_data("junk", 200);
+
+    generator.set_quality_rank("common", 1);
+    generator.set_quality_rank("rare", 2);
+    generator.set_quality_rank("legendary", 3);
+
+    assert_eq!(generator.get_quality_rank("legendary"), Some(3));
+    assert_eq!(generator.get_quality_rank("junk"), None);
+    assert_eq!(
+        generator.qualities_sorted_by_rank(),
+        vec!["common", "rare", "legendary", "junk"]
+    );
+}
+
+#[test]
+fn test_validate_flags_subtype_without_required_attributes() {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item_subtype("weapon", "axe", 1);
+    generator.set_item_subtype("weapon", "club", 0);
+    generator.set_attribute("weapon", "sword", ItemAttribute::new("damage", 10.0, 0.0, 100.0, true));
+    generator.set_attribute("weapon", "axe", ItemAttribute::new("crit", 1.0, 0.0, 100.0, false));
+
+    match generator.validate() {
+        Err(PraedaError::InvalidData(message)) => {
+            assert!(message.contains("weapon/axe"));
+            assert!(!message.contains("weapon/sword"));
+            // Unreachable subtypes are not flagged
+            assert!(!message.contains("weapon/club"));
+        }
+        other => panic!("expected validation error, got {:?}", other),
+    }
+
+    // A type-wide required attribute covers every subtype
+    generator.set_attribute("weapon", "", ItemAttribute::new("durability", 50.0, 0.0, 100.0, true));
+    assert!(generator.validate().is_ok());
+}
+
+#[test]
+fn test_load_inline_affixes_from_item_list() -> Result<()> {
+    let toml_str = r#"
+[quality_data]
+common = 1
+
+[[item_types]]
+item_type = "weapon"
+weight = 1
+[item_types.subtypes]
+sword = 1
+
+[[item_list]]
+item_type = "weapon"
+subtype = "sword"
+names = ["longsword"]
+
+[[item_list.prefixes]]
+name = "Flaming"
+[[item_list.prefixes.attributes]]
+name = "fire_damage"
+initial_value = 5.0
+min = 0.0
+max = 50.0
+required = true
+
+[[item_list.suffixes]]
+name = "of Speed"
+[[item_list.suffixes.attributes]]
+name = "speed"
+initial_value = 1.0
+min = 0.0
+max = 10.0
+required = true
+
+[[item_affixes]]
+item_type = "weapon"
+subtype = "sword"
+[[item_affixes.prefixes]]
+name = "Sharp"
+attributes = []
+    "#;
+
+    let mut generator = PraedaGenerator::new();
+    generator.load_data(toml_str)?;
+
+    let prefix_names: Vec<String> = generator
+        .get_prefixes("weapon", "sword")
+        .iter()
+        .map(|affix| affix.get_name().to_string())
+        .collect();
+    assert_eq!(prefix_names, vec!["Sharp", "Flaming"]);
+    assert_eq!(generator.get_suffixes("weapon", "sword").len(), 1);
+
+    let options = GeneratorOptions {
+        number_of_items: 30,
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "inline")?;
+    let flaming = items.iter().find(|item| item.get_prefix().get_name() == "Flaming").unwrap();
+    assert!(flaming.has_attribute("fire_damage"));
+    assert!(items.iter().all(|item| item.get_suffix().get_name() == "of Speed"));
+
+    Ok(())
+}
+
+#[test]
+fn test_quality_exclusive_attribute() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_quality_data("legendary", 5);
+    generator.set_quality_attribute(
+        "legendary",
+        "weapon",
+        "",
+        ItemAttribute::new("legendary_power", 50.0, 0.0, 1000.0, true),
+    );
+
+    let options = GeneratorOptions {
+        number_of_items: 10,
+        ..Default::default()
+    };
+    let legendary = GeneratorOverrides::new("legendary", "weapon", "");
+    let items = generator.generate_loot(&options, &legendary, "legendary")?;
+    assert!(items.iter().all(|item| item.has_attribute("legendary_power")));
+
+    let common = GeneratorOverrides::new("common", "weapon", "");
+    let items = generator.generate_loot(&options, &common, "common")?;
+    assert!(items.iter().all(|item| !item.has_attribute("legendary_power")));
+
+    Ok(())
+}
+
+#[test]
+fn test_quality_metadata_is_attached_to_items() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_quality_metadata("rare", "color", serde_json::json!("#0070dd"));
+    assert_eq!(generator.get_quality_metadata("rare", "color"), Some(&serde_json::json!("#0070dd")));
+
+    let options = GeneratorOptions {
+        number_of_items: 5,
+        ..Default::default()
+    };
+    let rare = GeneratorOverrides::new("rare", "", "");
+    let items = generator.generate_loot(&options, &rare, "rare")?;
+    assert!(items.iter().all(|item| item.get_metadata("color") == Some(&serde_json::json!("#0070dd"))));
+
+    let common = GeneratorOverrides::new("common", "", "");
+    let items = generator.generate_loot(&options, &common, "common")?;
+    assert!(items.iter().all(|item| !item.has_metadata("color")));
+
+    Ok(())
+}
+
+#[test]
+fn test_roll_range_attribute() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    let mut damage = ItemAttribute::new("damage", 0.0, 10.0, 20.0, true);
+    damage.set_roll_range(true);
+    assert!(damage.rolls_range());
+    generator.set_attribute("weapon", "sword", damage);
+
+    let mut bonus = ItemAttribute::new("fire_damage", 0.0, 1.0, 3.0, true);
+    bonus.set_roll_range(true);
+    generator.set_prefix_attribute("weapon", "sword", "Flaming", bonus);
+
+    let options = GeneratorOptions {
+        number_of_items: 200,
+        base_level: 50.0,
+        scaling_factor: 5.0,
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "rolls")?;
+
+    let damages: Vec<f64> = items
+        .iter()
+        .map(|item| item.get_attribute("damage").unwrap().get_initial_value())
+        .collect();
+    assert!(damages.iter().all(|d| (10.0..=20.0).contains(d)), "{:?}", damages);
+    assert!(damages.iter().any(|d| *d != damages[0]));
+
+    for item in &items {
+        let fire = item.get_attribute("fire_damage").unwrap().get_initial_value();
+        assert!((1.0..=3.0).contains(&fire));
+    }
+
+    // Without the flag, a zero initial value is scaled by level as before
+    let plain = ItemAttribute::new("damage", 0.0, 10.0, 20.0, true);
+    assert!(!plain.rolls_range());
+
+    Ok(())
+}
+
+#[test]
+fn test_subtype_level_offset() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item_type("accessory", 1);
+    generator.set_item_subtype("accessory", "ring", 1);
+    generator.set_subtype_level_offset("accessory", "ring", -5.0);
+    assert_eq!(generator.get_subtype_level_offset("accessory", "ring"), -5.0);
+    assert_eq!(generator.get_subtype_level_offset("weapon", "sword"), 0.0);
+
+    let options = GeneratorOptions {
+        number_of_items: 40,
+        base_level: 20.0,
+        ..merge_options()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "mixed")?;
+    let level_of = |item: &Item| item.get_attribute("level").unwrap().get_initial_value();
+
+    let rings: Vec<&Item> = items.iter().filter(|item| item.get_subtype() == "ring").collect();
+    let swords: Vec<&Item> = items.iter().filter(|item| item.get_subtype() == "sword").collect();
+    assert!(!rings.is_empty() && !swords.is_empty());
+    assert!(rings.iter().all(|item| level_of(item) == 15.0));
+    assert!(swords.iter().all(|item| level_of(item) == 20.0));
+
+    Ok(())
+}
+
+#[test]
+fn test_quality_level_bonus() -> Result<()> {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_quality_data("legendary", 1);
+    generator.set_quality_level_bonus("legendary", 5.0);
+    assert_eq!(generator.get_quality_level_bonus("common"), 0.0);
+
+    let level_of = |generator: &mut PraedaGenerator, quality: &str| -> Result<f64> {
+        let overrides = GeneratorOverrides::new(quality, "", "");
+        let items = generator.generate_loot(&merge_options(), &overrides, quality)?;
+        Ok(items[0].get_attribute("level").unwrap().get_initial_value())
+    };
+    assert_eq!(level_of(&mut generator, "common")?, 10.0);
+    assert_eq!(level_of(&mut generator, "legendary")?, 15.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_all_loot_json_contains_every_key() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 2,
+        seed: Some(5),
+        ..Default::default()
+    };
+    let chest = generator.generate_loot(&options, &GeneratorOverrides::empty(), "chest")?;
+    let boss = generator.generate_loot(&options, &GeneratorOverrides::empty(), "boss")?;
+
+    let json = generator.all_loot_json()?;
+    let parsed: HashMap<String, Vec<Item>> = serde_json::from_str(&json)?;
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed["chest"], chest);
+    assert_eq!(parsed["boss"], boss);
+
+    Ok(())
+}
+
+#[test]
+fn test_sort_by_quality_rank() -> Result<()> {
+    let mut generator = create_test_generator();
+    generator.set_quality_rank("common", 0);
+    generator.set_quality_rank("uncommon", 1);
+    generator.set_quality_rank("rare", 2);
+
+    let options = GeneratorOptions {
+        number_of_items: 40,
+        seed: Some(21),
+        sort: Some(SortKey::QualityRank),
+        ..Default::default()
+    };
+    let items = generator.generate_loot(&options, &GeneratorOverrides::empty(), "shop")?;
+    assert_eq!(generator.get_loot("shop"), items);
+
+    let ranks: Vec<u32> = items
+        .iter()
+        .map(|item| generator.get_quality_rank(item.get_quality()).unwrap())
+        .collect();
+    assert!(ranks.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert!(ranks.first() > ranks.last());
+    for pair in items.windows(2) {
+        if pair[0].get_quality() == pair[1].get_quality() {
+            assert!(pair[0].get_name() <= pair[1].get_name());
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_load_data_merge_keeps_existing_config() -> Result<()> {
+    let mut generator = PraedaGenerator::new();
+    generator.set_quality_data("cursed", 5);
+    generator.set_item_type("weapon", 1);
+    generator.set_item("weapon", "sword", vec!["rusty blade"]);
+    generator.set_prefix_attribute("weapon", "sword", "Sharp", ItemAttribute::new("damage", 2.0, 0.0, 5.0, true));
+
+    let toml = r#"
+        [quality_data]
+        common = 100
+        rare = 10
+
+        [[item_types]]
+        item_type = "weapon"
+        weight = 3
+        subtypes = { sword = 1 }
+
+        [[item_types]]
+        item_type = "armor"
+        weight = 2
+        subtypes = { head = 1 }
+
+        [[item_list]]
+        item_type = "weapon"
+        subtype = "sword"
+        names = ["longsword", "rusty blade"]
+
+        [[item_affixes]]
+        item_type = "weapon"
+        subtype = "sword"
+        prefixes = [{ name = "Sharp", attributes = [] }, { name = "Heavy", attributes = [] }]
+        suffixes = []
+    "#;
+    generator.load_data_merge(toml)?;
+
+    assert!(generator.has_quality("cursed"));
+    assert!(generator.has_quality("common"));
+    assert!(generator.has_quality("rare"));
+    assert_eq!(generator.get_item_type("weapon").unwrap().get_weight(), 3);
+    assert!(generator.has_item_type("armor"));
+    assert_eq!(
+        generator.get_item_names("weapon", "sword"),
+        vec!["rusty blade".to_string(), "longsword".to_string()]
+    );
+    let prefixes = generator.get_prefixes("weapon", "sword");
+    assert_eq!(prefixes.len(), 2);
+    assert!(prefixes[0].get_attributes().is_empty());
+    assert_eq!(prefixes[1].get_name(), "Heavy");
+
+    // A plain load discards the programmatic quality
+    generator.load_data(toml)?;
+    assert!(!generator.has_quality("cursed"));
+
+    Ok(())
+}
+
+#[test]
+fn test_unseeded_run_exposes_its_seed() -> Result<()> {
+    let mut generator = create_test_generator();
+    assert_eq!(generator.last_seed(), None);
+
+    let options = GeneratorOptions { number_of_items: 10, ..Default::default() };
+    let first = generator.generate_loot(&options, &GeneratorOverrides::empty(), "first")?;
+    let seed = generator.last_seed().unwrap();
+
+    let replay = GeneratorOptions { seed: Some(seed), ..options.clone() };
+    let second = generator.generate_loot(&replay, &GeneratorOverrides::empty(), "second")?;
+    assert_eq!(first, second);
+    assert_eq!(generator.last_seed(), Some(seed));
+
+    let (reported, report) = generator.generate_loot_reported(&options, &GeneratorOverrides::empty(), "third")?;
+    assert_eq!(generator.last_seed(), Some(report.seed));
+    let replay = GeneratorOptions { seed: Some(report.seed), ..options };
+    assert_eq!(generator.generate_loot(&replay, &GeneratorOverrides::empty(), "fourth")?, reported);
+
+    Ok(())
+}
+
+#[test]
+fn test_lenient_generation_applies_sort() {
+    let mut generator = create_attribute_merge_generator();
+    generator.set_item("weapon", "sword", vec!["longsword", "arming sword", "claymore"]);
+    let options = GeneratorOptions {
+        number_of_items: 30,
+        seed: Some(6),
+        level_variance: 5.0,
+        sort: Some(SortKey::Level),
+        ..merge_options()
+    };
+
+    let (items, errors) = generator.generate_loot_lenient(&options, &GeneratorOverrides::empty(), "lenient");
+    assert!(errors.is_empty());
+    assert_eq!(generator.get_loot("lenient"), items);
+    let levels: Vec<f64> = items
+        .iter()
+        .map(|item| item.get_attribute("level").unwrap().get_initial_value())
+        .collect();
+    assert!(levels.windows(2).all(|pair| pair[0] >= pair[1]));
+    assert!(levels.first() > levels.last());
+}
+
+#[test]
+fn test_generate_loot_json_pretty() -> Result<()> {
+    let mut generator = create_test_generator();
+    let options = GeneratorOptions {
+        number_of_items: 3,
+        ..Default::default()
+    };
+
+    let json = generator.generate_loot_json_pretty(&options, &GeneratorOverrides::empty(), "pretty")?;
+    assert!(json.contains('\n'));
+    let parsed: Vec<Item> = serde_json::from_str(&json)?;
+    assert_eq!(parsed, generator.get_loot("pretty"));
+
+    Ok(())
+}